@@ -0,0 +1,179 @@
+//! Admin tool of the tournament organisers.
+//!
+//! Creates the tournaments from TOML/JSON spec files, lists and shows them, pays out and
+//! cancels. The spec, prizes and winners files are checked with the rules of the contract
+//! before anything is sent, and `--dry-run` prints the `near call` of each change instead.
+
+mod near;
+mod spec;
+
+use std::path::PathBuf;
+use std::process;
+use clap::{Parser, Subcommand};
+use near_sdk::AccountId;
+use tournament_client::{TournamentClient, TournamentFilter, TournamentState};
+
+use crate::near::Near;
+use crate::spec::{check_payout, read_basis_points, read_file, read_winners, TournamentSpec};
+
+/// Page size of the roster
+const ROSTER_PAGE: u64 = 50;
+
+#[derive(Parser)]
+#[clap(name = "tournament", about = "Admin tool of the tournament contract")]
+struct Cli {
+    /// Account of the tournament contract
+    #[clap(long, env = "TOURNAMENT_CONTRACT")]
+    contract: AccountId,
+
+    /// Account signing the change calls (with near-cli)
+    #[clap(long, env = "TOURNAMENT_ACCOUNT")]
+    account: Option<AccountId>,
+
+    /// RPC node for the views
+    #[clap(long, env = "NEAR_RPC_URL", default_value = "https://rpc.testnet.near.org")]
+    rpc: String,
+
+    /// Print the change calls instead of sending them
+    #[clap(long)]
+    dry_run: bool,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a tournament from a spec file
+    Create {
+        spec: PathBuf,
+        /// Basis points map file replacing the one of the spec
+        #[clap(long)]
+        basis_points: Option<PathBuf>,
+    },
+    /// List the tournaments
+    List {
+        #[clap(long)]
+        from_index: Option<u128>,
+        #[clap(long)]
+        limit: Option<u64>,
+        /// open, completed or cancelled
+        #[clap(long)]
+        state: Option<String>,
+        #[clap(long)]
+        owner: Option<AccountId>,
+    },
+    /// Show a tournament
+    Show { tournament_id: String },
+    /// List the players (or the teams) of a tournament
+    Roster { tournament_id: String },
+    /// Cancel a tournament refunding the players and the sponsors
+    Cancel { tournament_id: String },
+    /// Reward the winners from a winners file, printing the prizes first
+    Payout {
+        tournament_id: String,
+        #[clap(long)]
+        winners: PathBuf,
+    },
+}
+
+fn parse_state(state: &str) -> Result<TournamentState, String> {
+    serde_json::from_value(serde_json::Value::String(state.to_string()))
+        .map_err(|_| format!("Unknown state {:?}", state))
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    let client = TournamentClient::new(cli.contract);
+    let near = Near {
+        rpc_url: cli.rpc,
+        account_id: cli.account,
+        dry_run: cli.dry_run,
+    };
+
+    match cli.command {
+        Command::Create { spec, basis_points } => {
+            let basis_points_map = basis_points.map(|path| read_basis_points(&path)).transpose()?;
+            let spec: TournamentSpec = read_file(&spec)?;
+            let guarantee = spec.guarantee()?;
+            let payout_tiers = spec.payout_tiers()?;
+            let args = spec.into_create_args(basis_points_map)?;
+            let tournament_id = args.tournament_id.clone();
+            near.call(&client.tournament_create(args).with_deposit(guarantee))?;
+            if payout_tiers.is_empty() {
+                return Ok(());
+            }
+            near.call(&client.set_payout_tiers(tournament_id, payout_tiers))
+        }
+        Command::List { from_index, limit, state, owner } => {
+            let filter = TournamentFilter {
+                state: state.as_deref().map(parse_state).transpose()?,
+                owner_id: owner,
+                ..Default::default()
+            };
+            let tournaments = near.view(&client.display_tournaments(from_index, limit, Some(filter), None))?;
+            for tournament in tournaments {
+                println!(
+                    "{}\t{}\t{:?}\t{}\t{}",
+                    tournament.tournament_id,
+                    tournament.metadata.name,
+                    tournament.state,
+                    tournament.prize_fond.0,
+                    tournament.owner_id,
+                );
+            }
+            Ok(())
+        }
+        Command::Show { tournament_id } => {
+            let tournament = near.view(&client.display_tournament(tournament_id.clone()))?
+                .ok_or_else(|| format!("No tournament {}", tournament_id))?;
+            println!("{}", serde_json::to_string_pretty(&tournament).unwrap());
+            Ok(())
+        }
+        Command::Roster { tournament_id } => {
+            let tournament = near.view(&client.display_tournament(tournament_id.clone()))?
+                .ok_or_else(|| format!("No tournament {}", tournament_id))?;
+            let mut from_index = 0;
+            loop {
+                let players = near.view(&client.tournament_players(tournament_id.clone(), Some(from_index), Some(ROSTER_PAGE)))?;
+                for player_id in &players {
+                    //the captains hold the seats of the teams
+                    if tournament.metadata.team_size.is_some() {
+                        if let Some(team) = near.view(&client.display_team(tournament_id.clone(), player_id.clone()))? {
+                            let members: Vec<String> = team.members.iter().map(|member| member.to_string()).collect();
+                            println!("{}\t{}\t{}", player_id, team.name, members.join(","));
+                            continue;
+                        }
+                    }
+                    println!("{}", player_id);
+                }
+                if (players.len() as u64) < ROSTER_PAGE {
+                    return Ok(());
+                }
+                from_index += ROSTER_PAGE as u128;
+            }
+        }
+        Command::Cancel { tournament_id } => {
+            near.call(&client.cancel_tournament(tournament_id))
+        }
+        Command::Payout { tournament_id, winners } => {
+            let winners_map = read_winners(&winners)?;
+            let tournament = near.view(&client.display_tournament(tournament_id.clone()))?
+                .ok_or_else(|| format!("No tournament {}", tournament_id))?;
+            check_payout(&tournament, &winners_map)?;
+            //the preview runs the checks of the payout except the owner one, the views have no caller
+            let preview = near.view(&client.preview_rewards(tournament_id.clone(), winners_map.clone()))?;
+            for prize in &preview.winners {
+                println!("{}\t{}\t{}", prize.place, prize.account_id, prize.amount.0);
+            }
+            println!("remainder\t{}", preview.remainder.0);
+            near.call(&client.reward_prizes(tournament_id, winners_map))
+        }
+    }
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,136 @@
+//! Sending the calls: views go to the RPC node, change calls are signed and sent by
+//! `near call` of near-cli, so the keys stay in its credentials.
+
+use std::process::Command;
+use near_sdk::AccountId;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use tournament_client::{Call, View};
+
+pub struct Near {
+    pub rpc_url: String,
+    //the account signing the change calls
+    pub account_id: Option<AccountId>,
+    //print the change calls instead of sending them
+    pub dry_run: bool,
+}
+
+//quote the argument for the shell
+fn shell_quote(argument: &str) -> String {
+    format!("'{}'", argument.replace('\'', r"'\''"))
+}
+
+impl Near {
+    pub fn view<R: DeserializeOwned>(&self, view: &View<R>) -> Result<R, String> {
+        let response: Value = ureq::post(&self.rpc_url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": "tournament-cli",
+                "method": "query",
+                "params": {
+                    "request_type": "call_function",
+                    "finality": "final",
+                    "account_id": view.contract_id(),
+                    "method_name": view.method_name(),
+                    "args_base64": base64::encode(view.args()),
+                },
+            }))
+            .map_err(|error| error.to_string())?
+            .into_json()
+            .map_err(|error| error.to_string())?;
+
+        if let Some(error) = response.get("error") {
+            return Err(format!("{} failed: {}", view.method_name(), error));
+        }
+        //the contract panics are returned as the result
+        if let Some(error) = response["result"].get("error") {
+            return Err(format!("{} failed: {}", view.method_name(), error));
+        }
+
+        let result: Vec<u8> = serde_json::from_value(response["result"]["result"].clone())
+            .map_err(|error| format!("{} returned no result: {}", view.method_name(), error))?;
+        view.decode(&result).map_err(|error| format!("{} returned {}", view.method_name(), error))
+    }
+
+    //the near-cli arguments of the change call
+    fn call_arguments<R>(&self, call: &Call<R>) -> Result<Vec<String>, String> {
+        let account_id = self.account_id.as_ref().ok_or("The signing account isn't set, pass --account")?;
+        let args = String::from_utf8(call.args().to_vec()).map_err(|_| "Arguments aren't JSON")?;
+        Ok(vec![
+            "call".to_string(),
+            call.contract_id().to_string(),
+            call.method_name().to_string(),
+            args,
+            "--accountId".to_string(),
+            account_id.to_string(),
+            "--gas".to_string(),
+            call.gas().to_string(),
+            "--depositYocto".to_string(),
+            call.deposit().to_string(),
+        ])
+    }
+
+    //the near-cli command line of the change call printed by the dry run
+    fn call_command<R>(&self, call: &Call<R>) -> Result<String, String> {
+        let quoted: Vec<String> = self.call_arguments(call)?.iter().map(|argument| shell_quote(argument)).collect();
+        Ok(format!("near {}", quoted.join(" ")))
+    }
+
+    pub fn call<R>(&self, call: &Call<R>) -> Result<(), String> {
+        if self.dry_run {
+            println!("{}", self.call_command(call)?);
+            return Ok(());
+        }
+
+        let arguments = self.call_arguments(call)?;
+
+        let status = Command::new("near")
+            .args(&arguments)
+            .status()
+            .map_err(|error| format!("Can't run near-cli: {}", error))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("{} failed", call.method_name()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tournament_client::TournamentClient;
+
+    fn near(account_id: Option<&str>) -> Near {
+        Near {
+            rpc_url: "https://rpc.testnet.near.org".to_string(),
+            account_id: account_id.map(|account_id| account_id.parse().unwrap()),
+            dry_run: true,
+        }
+    }
+
+    #[test]
+    fn dry_run_prints_quoted_command() {
+        let client = TournamentClient::new("tournament.testnet".parse().unwrap());
+        let call = client.participate_tournament("it's-1".to_string(), None, 1);
+
+        assert_eq!(
+            near(Some("player.testnet")).call_command(&call).unwrap(),
+            format!(
+                r#"near 'call' 'tournament.testnet' 'participate_tournament' '{{"tournament_id":"it'\''s-1"}}' '--accountId' 'player.testnet' '--gas' '{}' '--depositYocto' '1'"#,
+                call.gas(),
+            ),
+        );
+    }
+
+    #[test]
+    fn dry_run_needs_account() {
+        let client = TournamentClient::new("tournament.testnet".parse().unwrap());
+        let call = client.participate_tournament("tournament-1".to_string(), None, 1);
+
+        assert_eq!(
+            near(None).call(&call).unwrap_err(),
+            "The signing account isn't set, pass --account",
+        );
+    }
+}
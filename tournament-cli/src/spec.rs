@@ -0,0 +1,388 @@
+//! Spec files of the tournaments, prizes and winners (TOML or JSON by the extension),
+//! checked with the same rules the contract uses.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use near_sdk::AccountId;
+use near_sdk::json_types::{Base64VecU8, U64};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tournament_client::{EntryGate, JsonTournament, PayoutTier, TournamentAccess, TournamentCreateArgs, TournamentMetadata, TournamentState};
+use tournament_test::tournament::validation::{check_basis_points, check_payout_tiers, check_schedule, check_winners};
+
+const YOCTO_PER_NEAR: u128 = 1_000_000_000_000_000_000_000_000;
+
+/// A tournament to create
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TournamentSpec {
+    pub tournament_id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+    pub game: Option<String>,
+    pub reference: Option<String>,
+    //base64-encoded sha256 hash of the reference
+    pub reference_hash: Option<String>,
+    pub links: Option<Vec<String>>,
+    pub extra: Option<String>,
+    //nanoseconds since epoch
+    pub starts_at: Option<u64>,
+    pub ends_at: Option<u64>,
+    pub players_number: u32,
+    //members of each team, the players number counts the teams
+    pub team_size: Option<u8>,
+    //"public", "allowlist" or "invite_code"
+    pub access: Option<TournamentAccess>,
+    //the players have to hold a token of one of the gates: {nft = {contract_id}} or {ft = {contract_id, min_balance}}
+    pub gates: Option<Vec<EntryGate>>,
+    //yoctoNEAR or "<amount> NEAR"
+    pub in_price: String,
+    //prize fond guaranteed by the owner, attached to the creation: yoctoNEAR or "<amount> NEAR"
+    pub guarantee: Option<String>,
+    pub tournament_owner_id: AccountId,
+    //the prize of each place in basis points (10000 is the whole prize fond), can be also given in a separate file
+    #[serde(default)]
+    pub basis_points_map: HashMap<String, u16>,
+    //prizes replacing the basis points map when the number of entrants is in the range of the tier
+    #[serde(default)]
+    pub payout_tiers: Vec<PayoutTierSpec>,
+}
+
+/// Prizes of the tournament with the number of entrants in the range
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct PayoutTierSpec {
+    pub min_entrants: u32,
+    pub max_entrants: u32,
+    pub basis_points_map: HashMap<String, u16>,
+}
+
+//read a TOML or JSON file
+pub fn read_file<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let content = fs::read_to_string(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::from_str(&content).map_err(|error| format!("{}: {}", path.display(), error)),
+        Some("json") => serde_json::from_str(&content).map_err(|error| format!("{}: {}", path.display(), error)),
+        _ => Err(format!("{}: expected a .toml or .json file", path.display())),
+    }
+}
+
+//parse yoctoNEAR or "<amount> NEAR"
+pub fn parse_amount(amount: &str) -> Result<u128, String> {
+    let invalid = || format!("Invalid amount {:?}", amount);
+
+    let near = match amount.trim().strip_suffix("NEAR") {
+        Some(near) => near.trim(),
+        None => return amount.trim().parse().map_err(|_| invalid()),
+    };
+
+    let (whole, fraction) = near.split_once('.').unwrap_or((near, ""));
+    if fraction.len() > 24 || !fraction.chars().all(|digit| digit.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let whole: u128 = whole.parse().map_err(|_| invalid())?;
+    let fraction: u128 = if fraction.is_empty() {
+        0
+    } else {
+        format!("{:0<24}", fraction).parse().map_err(|_| invalid())?
+    };
+
+    whole.checked_mul(YOCTO_PER_NEAR)
+        .and_then(|whole| whole.checked_add(fraction))
+        .ok_or_else(invalid)
+}
+
+//keys of the spec maps are the places
+fn parse_places<V>(map: HashMap<String, V>) -> Result<HashMap<u32, V>, String> {
+    map.into_iter()
+        .map(|(place, value)| {
+            let place = place.parse().map_err(|_| format!("Invalid place {:?}", place))?;
+            Ok((place, value))
+        })
+        .collect()
+}
+
+pub fn read_basis_points(path: &Path) -> Result<HashMap<u32,u16>, String> {
+    parse_places(read_file(path)?)
+}
+
+pub fn read_winners(path: &Path) -> Result<HashMap<u32,AccountId>, String> {
+    parse_places(read_file(path)?)
+}
+
+impl TournamentSpec {
+    //check the payout tiers and turn them into the arguments of `set_payout_tiers`
+    pub fn payout_tiers(&self) -> Result<Vec<PayoutTier>, String> {
+        let mut payout_tiers = self.payout_tiers.iter()
+            .map(|tier| Ok(PayoutTier {
+                min_entrants: tier.min_entrants,
+                max_entrants: tier.max_entrants,
+                basis_points_map: parse_places(tier.basis_points_map.clone())?,
+            }))
+            .collect::<Result<Vec<PayoutTier>, String>>()?;
+
+        payout_tiers.sort_by_key(|tier| tier.min_entrants);
+        check_payout_tiers(&payout_tiers, self.players_number)?;
+        Ok(payout_tiers)
+    }
+
+    //the deposit attached to `tournament_create`
+    pub fn guarantee(&self) -> Result<u128, String> {
+        self.guarantee.as_deref().map_or(Ok(0), parse_amount)
+    }
+
+    //check the spec and turn it into the arguments of `tournament_create`
+    pub fn into_create_args(self, basis_points_map: Option<HashMap<u32,u16>>) -> Result<TournamentCreateArgs, String> {
+        let in_price = parse_amount(&self.in_price)?;
+        let basis_points_map = match basis_points_map {
+            Some(basis_points_map) => basis_points_map,
+            None => parse_places(self.basis_points_map)?,
+        };
+        let reference_hash = self.reference_hash
+            .map(|hash| base64::decode(&hash).map(Base64VecU8).map_err(|_| "Reference hash should be base64".to_string()))
+            .transpose()?;
+        let starts_at = self.starts_at.map(U64);
+        let ends_at = self.ends_at.map(U64);
+
+        check_basis_points(&basis_points_map, self.players_number)?;
+        check_schedule(&starts_at, &ends_at)?;
+
+        let metadata = TournamentMetadata {
+            name: self.name,
+            icon: self.icon,
+            description: self.description,
+            game: self.game,
+            reference: self.reference,
+            reference_hash,
+            links: self.links,
+            extra: self.extra,
+            starts_at,
+            ends_at,
+            players_number: self.players_number,
+            in_price,
+            team_size: self.team_size,
+            access: self.access.unwrap_or_default(),
+            gates: self.gates.filter(|gates| !gates.is_empty()),
+        };
+        metadata.validate()?;
+
+        Ok(TournamentCreateArgs {
+            tournament_id: self.tournament_id,
+            name: metadata.name,
+            icon: metadata.icon,
+            description: metadata.description,
+            game: metadata.game,
+            reference: metadata.reference,
+            reference_hash: metadata.reference_hash,
+            links: metadata.links,
+            extra: metadata.extra,
+            starts_at: metadata.starts_at,
+            ends_at: metadata.ends_at,
+            players_number: metadata.players_number,
+            team_size: metadata.team_size,
+            access: self.access,
+            gates: metadata.gates,
+            in_price: in_price.into(),
+            tournament_owner_id: self.tournament_owner_id,
+            basis_points_map,
+        })
+    }
+}
+
+//check the winners against the tournament, the view shows the places prizes of the active tier
+//or the first three places of the base prizes, the places below them are checked against the players number
+pub fn check_payout(tournament: &JsonTournament, winners_map: &HashMap<u32,AccountId>) -> Result<(), String> {
+    if tournament.state != TournamentState::Open {
+        return Err("Tournament is inactive".to_string());
+    }
+    let active_tier = tournament.active_payout_tier.and_then(|index| tournament.payout_tiers.get(index as usize));
+    check_winners(winners_map, |place| match (active_tier, place) {
+        (Some(tier), _) => matches!(tier.basis_points_map.get(&place), Some(basis_points) if *basis_points > 0),
+        (None, 1) => tournament.first_place_prize.0 > 0,
+        (None, 2) => tournament.second_place_prize.0 > 0,
+        (None, 3) => tournament.third_place_prize.0 > 0,
+        (None, _) => place <= tournament.metadata.players_number,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(toml: &str) -> TournamentSpec {
+        toml::from_str(toml).unwrap()
+    }
+
+    const SPEC: &str = r#"
+        tournament_id = "tournament-1"
+        name = "Tournament-1"
+        icon = "https://example.com/icon.png"
+        players_number = 8
+        in_price = "0.1 NEAR"
+        tournament_owner_id = "dgstai.testnet"
+
+        [basis_points_map]
+        1 = 5000
+        2 = 3000
+        3 = 1000
+    "#;
+
+    #[test]
+    fn parse_amounts() {
+        assert_eq!(parse_amount("100000000000000000000000"), Ok(YOCTO_PER_NEAR / 10));
+        assert_eq!(parse_amount("0.1 NEAR"), Ok(YOCTO_PER_NEAR / 10));
+        assert_eq!(parse_amount("2NEAR"), Ok(2 * YOCTO_PER_NEAR));
+        assert!(parse_amount("0.1.2 NEAR").is_err());
+        assert!(parse_amount("-1").is_err());
+    }
+
+    #[test]
+    fn spec_into_create_args() {
+        let args = spec(SPEC).into_create_args(None).unwrap();
+
+        assert_eq!(args.in_price.0, YOCTO_PER_NEAR / 10);
+        assert_eq!(args.basis_points_map.get(&1), Some(&5_000));
+        assert_eq!(args.basis_points_map.len(), 3);
+        assert_eq!(args.icon.as_deref(), Some("https://example.com/icon.png"));
+    }
+
+    #[test]
+    fn basis_points_file_replaces_spec_basis_points() {
+        let mut basis_points_map = HashMap::new();
+        basis_points_map.insert(1, 10_000);
+
+        let args = spec(SPEC).into_create_args(Some(basis_points_map)).unwrap();
+        assert_eq!(args.basis_points_map.len(), 1);
+    }
+
+    #[test]
+    fn spec_qualifier_above_255_players() {
+        let qualifier = SPEC.replace("players_number = 8", "players_number = 4096")
+            .replace("3 = 1000", "3 = 1000\n        1000 = 5");
+        let args = spec(&qualifier).into_create_args(None).unwrap();
+        assert_eq!(args.players_number, 4_096);
+        assert_eq!(args.basis_points_map.get(&1_000), Some(&5));
+    }
+
+    #[test]
+    fn spec_free_tournament() {
+        let mut free = spec(SPEC);
+        free.in_price = "0".to_string();
+        assert_eq!(free.into_create_args(None).unwrap().in_price.0, 0);
+    }
+
+    #[test]
+    fn spec_guarantee() {
+        assert_eq!(spec(SPEC).guarantee(), Ok(0));
+        assert_eq!(spec(&format!("guarantee = \"5 NEAR\"\n{}", SPEC)).guarantee(), Ok(5 * YOCTO_PER_NEAR));
+        assert!(spec(&format!("guarantee = \"five\"\n{}", SPEC)).guarantee().is_err());
+    }
+
+    #[test]
+    fn spec_payout_tiers() {
+        assert!(spec(SPEC).payout_tiers().unwrap().is_empty());
+
+        let tiers = r#"
+            [[payout_tiers]]
+            min_entrants = 5
+            max_entrants = 8
+            basis_points_map = { 1 = 5000, 2 = 3000, 3 = 2000 }
+
+            [[payout_tiers]]
+            min_entrants = 2
+            max_entrants = 4
+            basis_points_map = { 1 = 10000 }
+        "#;
+        let payout_tiers = spec(&format!("{}\n{}", SPEC, tiers)).payout_tiers().unwrap();
+        assert_eq!(payout_tiers.iter().map(|tier| tier.min_entrants).collect::<Vec<u32>>(), vec![2, 5]);
+        assert_eq!(payout_tiers[1].basis_points_map.get(&3), Some(&2_000));
+
+        let overlapping = tiers.replace("max_entrants = 4", "max_entrants = 5");
+        assert_eq!(
+            spec(&format!("{}\n{}", SPEC, overlapping)).payout_tiers().unwrap_err(),
+            "Entrants range 5-8 overlaps the previous one",
+        );
+    }
+
+    #[test]
+    fn spec_checked_as_contract() {
+        let mut invalid = spec(SPEC);
+        invalid.players_number = 2;
+        assert_eq!(invalid.into_create_args(None).unwrap_err(), "Place 3 is out of the players number");
+
+        let mut invalid = spec(SPEC);
+        invalid.icon = Some("ftp://example.com/icon.png".to_string());
+        assert_eq!(invalid.into_create_args(None).unwrap_err(), "Icon should be an http(s), ipfs or ar URL");
+
+        let mut invalid = spec(SPEC);
+        invalid.team_size = Some(0);
+        assert_eq!(invalid.into_create_args(None).unwrap_err(), "Team size should be more than zero");
+
+        let mut invalid = spec(SPEC);
+        invalid.starts_at = Some(2);
+        invalid.ends_at = Some(1);
+        assert_eq!(invalid.into_create_args(None).unwrap_err(), "Tournament should end after it starts");
+    }
+
+    fn tournament(state: &str, active_payout_tier: Option<u8>) -> JsonTournament {
+        serde_json::from_value(serde_json::json!({
+            "tournament_id": "tournament-1",
+            "owner_id": "dgstai.testnet",
+            "metadata": { "name": "Tournament-1", "players_number": 8, "in_price": 100 },
+            "first_place_prize": "5000",
+            "second_place_prize": "3000",
+            "third_place_prize": "0",
+            "active": state == "open",
+            "state": state,
+            "prize_fond": "400",
+            "effective_prize_fond": "400",
+            "payout_tiers": [{ "min_entrants": 2, "max_entrants": 8, "basis_points_map": { "1": 10000 } }],
+            "active_payout_tier": active_payout_tier,
+        })).unwrap()
+    }
+
+    fn winners(places: &[u32]) -> HashMap<u32,AccountId> {
+        places.iter().map(|place| (*place, "alice.testnet".parse().unwrap())).collect()
+    }
+
+    #[test]
+    fn payout_checked_as_contract() {
+        assert_eq!(check_payout(&tournament("open", None), &winners(&[1, 2, 5])), Ok(()));
+        assert_eq!(check_payout(&tournament("open", None), &winners(&[3])).unwrap_err(), "No prize for the place 3");
+        assert_eq!(check_payout(&tournament("open", None), &winners(&[9])).unwrap_err(), "No prize for the place 9");
+        assert_eq!(check_payout(&tournament("open", Some(0)), &winners(&[2])).unwrap_err(), "No prize for the place 2");
+        assert_eq!(check_payout(&tournament("completed", None), &winners(&[1])).unwrap_err(), "Tournament is inactive");
+    }
+
+    #[test]
+    fn spec_access() {
+        assert_eq!(spec(SPEC).into_create_args(None).unwrap().access, None);
+
+        let args = spec(&format!("access = \"invite_code\"\n{}", SPEC)).into_create_args(None).unwrap();
+        assert_eq!(args.access, Some(TournamentAccess::InviteCode));
+
+        assert!(toml::from_str::<TournamentSpec>(&format!("access = \"private\"\n{}", SPEC)).is_err());
+    }
+
+    #[test]
+    fn spec_gates() {
+        assert_eq!(spec(SPEC).into_create_args(None).unwrap().gates, None);
+
+        let gates = "gates = [{ nft = { contract_id = \"guild-nft.testnet\" } }, { ft = { contract_id = \"guild-ft.testnet\", min_balance = \"1000\" } }]";
+        let args = spec(&format!("{}\n{}", gates, SPEC)).into_create_args(None).unwrap();
+        assert_eq!(args.gates, Some(vec![
+            EntryGate::Nft { contract_id: "guild-nft.testnet".parse().unwrap() },
+            EntryGate::Ft { contract_id: "guild-ft.testnet".parse().unwrap(), min_balance: near_sdk::json_types::U128(1_000) },
+        ]));
+
+        let team_spec = format!("{}\nteam_size = 2\n{}", gates, SPEC);
+        assert_eq!(spec(&team_spec).into_create_args(None).unwrap_err(), "Entry gates are for solo tournaments only");
+    }
+
+    #[test]
+    fn unknown_spec_fields() {
+        assert!(toml::from_str::<TournamentSpec>(&format!("players = 4\n{}", SPEC)).is_err());
+    }
+}
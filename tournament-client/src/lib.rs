@@ -0,0 +1,498 @@
+//! Typed client of the tournament contract.
+//!
+//! Builds the change and view calls of the contract with their JSON arguments and decodes
+//! the returned values into the contract types. It doesn't talk to a node: hand the method
+//! name, the arguments, the deposit and the gas of a `Call` (or a `View`) to the RPC client
+//! or signer of the service, and the bytes it returns to `decode`.
+//!
+//! ```
+//! use tournament_client::TournamentClient;
+//!
+//! let client = TournamentClient::new("tournament.testnet".parse().unwrap());
+//! let call = client.participate_tournament("tournament-1".to_string(), None, 1);
+//! assert_eq!(call.method_name(), "participate_tournament");
+//! assert_eq!(call.args(), br#"{"tournament_id":"tournament-1"}"#);
+//! ```
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use near_sdk::AccountId;
+use near_sdk::json_types::{U64, U128, Base64VecU8};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+pub use tournament_test::tournament::{
+    JsonPrize, JsonRewardsPreview, JsonSponsor, JsonTeam, JsonTournament, JsonTournamentStats, TournamentAccess, EntryGate, PayoutTier,
+    TournamentContractMetadata, TournamentCreateArgs, TournamentFilter, TournamentId, TournamentMetadata, TournamentMetadataUpdate,
+    TournamentState,
+};
+pub use tournament_test::upgrade::JsonStagedUpgrade;
+pub use tournament_test::payout::{compute_payout, PayoutError, PayoutPlan, PrizeTransfer};
+
+/// Gas attached to the calls by default (30 TGas)
+pub const DEFAULT_GAS: u64 = 30_000_000_000_000;
+
+/// Gas attached to the calls paying out to many accounts (100 TGas)
+pub const PAYOUT_GAS: u64 = 100_000_000_000_000;
+
+/// Gas to attach with `Call::with_gas` to the entrances of the gated tournaments,
+/// the gate contracts are checked before the seat is taken (100 TGas)
+pub const GATED_ENTRANCE_GAS: u64 = 100_000_000_000_000;
+
+/// Gas attached to the upgrade calls, the most of it is passed to `migrate` (300 TGas)
+pub const UPGRADE_GAS: u64 = 300_000_000_000_000;
+
+//methods without a return value return no bytes
+fn decode<R: DeserializeOwned>(result: &[u8]) -> serde_json::Result<R> {
+    if result.is_empty() {
+        serde_json::from_slice(b"null")
+    } else {
+        serde_json::from_slice(result)
+    }
+}
+
+fn to_args(args: Value) -> Vec<u8> {
+    serde_json::to_vec(&args).unwrap()
+}
+
+//the invite code is only sent to enter the invite code tournaments
+fn with_invite_code(mut args: Value, invite_code: Option<String>) -> Value {
+    if let Some(invite_code) = invite_code {
+        args["invite_code"] = json!(invite_code);
+    }
+    args
+}
+
+/// A change call of the contract returning `R`
+#[derive(Debug, Clone)]
+pub struct Call<R> {
+    contract_id: AccountId,
+    method_name: &'static str,
+    args: Vec<u8>,
+    deposit: u128,
+    gas: u64,
+    result: PhantomData<fn() -> R>,
+}
+
+impl<R> Call<R> {
+    fn new(contract_id: &AccountId, method_name: &'static str, args: Vec<u8>) -> Self {
+        Self {
+            contract_id: contract_id.clone(),
+            method_name,
+            args,
+            deposit: 0,
+            gas: DEFAULT_GAS,
+            result: PhantomData,
+        }
+    }
+
+    /// Attach the deposit (yoctoNEAR)
+    pub fn with_deposit(mut self, deposit: u128) -> Self {
+        self.deposit = deposit;
+        self
+    }
+
+    /// Attach the gas
+    pub fn with_gas(mut self, gas: u64) -> Self {
+        self.gas = gas;
+        self
+    }
+
+    pub fn contract_id(&self) -> &AccountId {
+        &self.contract_id
+    }
+
+    pub fn method_name(&self) -> &'static str {
+        self.method_name
+    }
+
+    /// Arguments of the call, JSON except for the upgrade calls taking the raw wasm code
+    pub fn args(&self) -> &[u8] {
+        &self.args
+    }
+
+    pub fn deposit(&self) -> u128 {
+        self.deposit
+    }
+
+    pub fn gas(&self) -> u64 {
+        self.gas
+    }
+}
+
+impl<R: DeserializeOwned> Call<R> {
+    /// Decode the value returned by the call
+    pub fn decode(&self, result: &[u8]) -> serde_json::Result<R> {
+        decode(result)
+    }
+}
+
+/// A view call of the contract returning `R`
+#[derive(Debug, Clone)]
+pub struct View<R> {
+    contract_id: AccountId,
+    method_name: &'static str,
+    args: Vec<u8>,
+    result: PhantomData<fn() -> R>,
+}
+
+impl<R> View<R> {
+    fn new(contract_id: &AccountId, method_name: &'static str, args: Vec<u8>) -> Self {
+        Self {
+            contract_id: contract_id.clone(),
+            method_name,
+            args,
+            result: PhantomData,
+        }
+    }
+
+    pub fn contract_id(&self) -> &AccountId {
+        &self.contract_id
+    }
+
+    pub fn method_name(&self) -> &'static str {
+        self.method_name
+    }
+
+    /// JSON arguments of the view call
+    pub fn args(&self) -> &[u8] {
+        &self.args
+    }
+}
+
+impl<R: DeserializeOwned> View<R> {
+    /// Decode the value returned by the view call
+    pub fn decode(&self, result: &[u8]) -> serde_json::Result<R> {
+        decode(result)
+    }
+}
+
+/// Builds the calls of the tournament contract deployed to `contract_id`
+#[derive(Debug, Clone)]
+pub struct TournamentClient {
+    contract_id: AccountId,
+}
+
+impl TournamentClient {
+    pub fn new(contract_id: AccountId) -> Self {
+        Self { contract_id }
+    }
+
+    pub fn contract_id(&self) -> &AccountId {
+        &self.contract_id
+    }
+
+    fn call<R: DeserializeOwned>(&self, method_name: &'static str, args: Value) -> Call<R> {
+        Call::new(&self.contract_id, method_name, to_args(args))
+    }
+
+    fn view<R: DeserializeOwned>(&self, method_name: &'static str, args: Value) -> View<R> {
+        View::new(&self.contract_id, method_name, to_args(args))
+    }
+
+    //initialization
+
+    pub fn new_default_meta(&self, owner_id: AccountId) -> Call<()> {
+        self.call("new_default_meta", json!({ "owner_id": owner_id }))
+    }
+
+    pub fn init(&self, owner_id: AccountId, metadata: TournamentContractMetadata) -> Call<()> {
+        self.call("new", json!({ "owner_id": owner_id, "metadata": metadata }))
+    }
+
+    //tournaments
+
+    /// Create the tournament, the deposit attached with `with_deposit` is the prize fond guaranteed by the owner
+    pub fn tournament_create(&self, args: TournamentCreateArgs) -> Call<()> {
+        self.call("tournament_create", json!({ "args": args }))
+    }
+
+    pub fn tournament_update(&self, tournament_id: TournamentId, update: TournamentMetadataUpdate) -> Call<()> {
+        self.call("tournament_update", json!({ "tournament_id": tournament_id, "update": update }))
+    }
+
+    pub fn display_tournament(&self, tournament_id: TournamentId) -> View<Option<JsonTournament>> {
+        self.view("display_tournament", json!({ "tournament_id": tournament_id }))
+    }
+
+    /// Join the tournament attaching `deposit`, the part above the in price is refunded.
+    /// Entrances of the gated tournaments need `with_gas(GATED_ENTRANCE_GAS)`
+    pub fn participate_tournament(&self, tournament_id: TournamentId, invite_code: Option<String>, deposit: u128) -> Call<()> {
+        self.call("participate_tournament", with_invite_code(json!({ "tournament_id": tournament_id }), invite_code))
+            .with_deposit(deposit)
+    }
+
+    /// Pay the entrance of `player_id` attaching `deposit`, the refunds of the seat come back to the signer
+    pub fn participate_tournament_for(
+        &self,
+        tournament_id: TournamentId,
+        player_id: AccountId,
+        invite_code: Option<String>,
+        deposit: u128,
+    ) -> Call<()> {
+        self.call("participate_tournament_for", with_invite_code(json!({ "tournament_id": tournament_id, "player_id": player_id }), invite_code))
+            .with_deposit(deposit)
+    }
+
+    /// The account that paid for the seat of the player
+    pub fn display_payer(&self, tournament_id: TournamentId, player_id: AccountId) -> View<Option<AccountId>> {
+        self.view("display_payer", json!({ "tournament_id": tournament_id, "player_id": player_id }))
+    }
+
+    pub fn display_freeplaces_in_tournament(&self, tournament_id: TournamentId) -> View<Option<U64>> {
+        self.view("display_freeplaces_in_tournament", json!({ "tournament_id": tournament_id }))
+    }
+
+    pub fn reward_prizes(&self, tournament_id: TournamentId, winners_map: HashMap<u32,AccountId>) -> Call<()> {
+        self.call("reward_prizes", json!({ "tournament_id": tournament_id, "winners_map": winners_map }))
+            .with_gas(PAYOUT_GAS)
+    }
+
+    pub fn preview_rewards(&self, tournament_id: TournamentId, winners_map: HashMap<u32,AccountId>) -> View<JsonRewardsPreview> {
+        self.view("preview_rewards", json!({ "tournament_id": tournament_id, "winners_map": winners_map }))
+    }
+
+    pub fn cancel_tournament(&self, tournament_id: TournamentId) -> Call<()> {
+        self.call("cancel_tournament", json!({ "tournament_id": tournament_id }))
+            .with_gas(PAYOUT_GAS)
+    }
+
+    pub fn leave_tournament(&self, tournament_id: TournamentId) -> Call<()> {
+        self.call("leave_tournament", json!({ "tournament_id": tournament_id }))
+    }
+
+    /// Give up the seat paid for `player_id` with `participate_tournament_for`, the deposit goes back to the payer
+    pub fn withdraw_sponsored_seat(&self, tournament_id: TournamentId, player_id: AccountId) -> Call<()> {
+        self.call("withdraw_sponsored_seat", json!({ "tournament_id": tournament_id, "player_id": player_id }))
+    }
+
+    pub fn withdraw_fees(&self, tournament_id: TournamentId) -> Call<()> {
+        self.call("withdraw_fees", json!({ "tournament_id": tournament_id }))
+    }
+
+    /// Add `deposit` to the prize fond, it's refunded if the tournament is cancelled
+    pub fn sponsor_tournament(&self, tournament_id: TournamentId, deposit: u128) -> Call<()> {
+        self.call("sponsor_tournament", json!({ "tournament_id": tournament_id }))
+            .with_deposit(deposit)
+    }
+
+    /// Replace the prizes by the number of entrants, an empty list removes the tiers
+    pub fn set_payout_tiers(&self, tournament_id: TournamentId, payout_tiers: Vec<PayoutTier>) -> Call<()> {
+        self.call("set_payout_tiers", json!({ "tournament_id": tournament_id, "payout_tiers": payout_tiers }))
+    }
+
+    //teams
+
+    /// Register the team of the signer (its captain) attaching `deposit` for the team's seat,
+    /// the prize is split equally between the members without `shares`
+    pub fn register_team(
+        &self,
+        tournament_id: TournamentId,
+        name: String,
+        members: Vec<AccountId>,
+        shares: Option<Vec<u16>>,
+        invite_code: Option<String>,
+        deposit: u128,
+    ) -> Call<()> {
+        self.call("register_team", with_invite_code(json!({
+            "tournament_id": tournament_id,
+            "name": name,
+            "members": members,
+            "shares": shares,
+        }), invite_code))
+        .with_deposit(deposit)
+    }
+
+    pub fn update_team(
+        &self,
+        tournament_id: TournamentId,
+        name: Option<String>,
+        members: Option<Vec<AccountId>>,
+        shares: Option<Vec<u16>>,
+    ) -> Call<()> {
+        self.call("update_team", json!({
+            "tournament_id": tournament_id,
+            "name": name,
+            "members": members,
+            "shares": shares,
+        }))
+    }
+
+    pub fn display_team(&self, tournament_id: TournamentId, captain_id: AccountId) -> View<Option<JsonTeam>> {
+        self.view("display_team", json!({ "tournament_id": tournament_id, "captain_id": captain_id }))
+    }
+
+    //access
+
+    pub fn allowlist_add(&self, tournament_id: TournamentId, account_ids: Vec<AccountId>) -> Call<()> {
+        self.call("allowlist_add", json!({ "tournament_id": tournament_id, "account_ids": account_ids }))
+    }
+
+    pub fn allowlist_remove(&self, tournament_id: TournamentId, account_ids: Vec<AccountId>) -> Call<()> {
+        self.call("allowlist_remove", json!({ "tournament_id": tournament_id, "account_ids": account_ids }))
+    }
+
+    pub fn is_allowed(&self, tournament_id: TournamentId, account_id: AccountId) -> View<bool> {
+        self.view("is_allowed", json!({ "tournament_id": tournament_id, "account_id": account_id }))
+    }
+
+    /// Add the invite codes by the sha256 hashes of the codes, the codes themselves aren't sent
+    pub fn add_invite_codes(&self, tournament_id: TournamentId, code_hashes: Vec<Vec<u8>>, single_use: bool) -> Call<()> {
+        let code_hashes: Vec<Base64VecU8> = code_hashes.into_iter().map(Base64VecU8).collect();
+        self.call("add_invite_codes", json!({
+            "tournament_id": tournament_id,
+            "code_hashes": code_hashes,
+            "single_use": single_use,
+        }))
+    }
+
+    pub fn remove_invite_codes(&self, tournament_id: TournamentId, code_hashes: Vec<Vec<u8>>) -> Call<()> {
+        let code_hashes: Vec<Base64VecU8> = code_hashes.into_iter().map(Base64VecU8).collect();
+        self.call("remove_invite_codes", json!({ "tournament_id": tournament_id, "code_hashes": code_hashes }))
+    }
+
+    pub fn is_invite_code_valid(&self, tournament_id: TournamentId, invite_code: String) -> View<bool> {
+        self.view("is_invite_code_valid", json!({ "tournament_id": tournament_id, "invite_code": invite_code }))
+    }
+
+    //enumeration
+
+    pub fn display_tournaments(
+        &self,
+        from_index: Option<u128>,
+        limit: Option<u64>,
+        filter: Option<TournamentFilter>,
+        reverse: Option<bool>,
+    ) -> View<Vec<JsonTournament>> {
+        self.view("display_tournaments", json!({
+            "from_index": from_index.map(U128),
+            "limit": limit,
+            "filter": filter,
+            "reverse": reverse,
+        }))
+    }
+
+    pub fn tournaments_total_supply(&self) -> View<U128> {
+        self.view("tournaments_total_supply", json!({}))
+    }
+
+    pub fn tournament_stats(&self) -> View<JsonTournamentStats> {
+        self.view("tournament_stats", json!({}))
+    }
+
+    pub fn tournament_supply_for_owner(&self, account_id: AccountId) -> View<U128> {
+        self.view("tournament_supply_for_owner", json!({ "account_id": account_id }))
+    }
+
+    pub fn tournaments_for_owner(&self, account_id: AccountId, from_index: Option<u128>, limit: Option<u64>) -> View<Vec<JsonTournament>> {
+        self.view("tournaments_for_owner", json!({
+            "account_id": account_id,
+            "from_index": from_index.map(U128),
+            "limit": limit,
+        }))
+    }
+
+    pub fn tournament_supply_for_state(&self, state: TournamentState) -> View<U128> {
+        self.view("tournament_supply_for_state", json!({ "state": state }))
+    }
+
+    pub fn tournaments_for_state(&self, state: TournamentState, from_index: Option<u128>, limit: Option<u64>) -> View<Vec<JsonTournament>> {
+        self.view("tournaments_for_state", json!({
+            "state": state,
+            "from_index": from_index.map(U128),
+            "limit": limit,
+        }))
+    }
+
+    pub fn tournament_players(&self, tournament_id: TournamentId, from_index: Option<u128>, limit: Option<u64>) -> View<Vec<AccountId>> {
+        self.view("tournament_players", json!({
+            "tournament_id": tournament_id,
+            "from_index": from_index.map(U128),
+            "limit": limit,
+        }))
+    }
+
+    //ownership
+
+    pub fn tournament_propose_owner(&self, tournament_id: TournamentId, new_owner_id: AccountId) -> Call<()> {
+        self.call("tournament_propose_owner", json!({ "tournament_id": tournament_id, "new_owner_id": new_owner_id }))
+    }
+
+    pub fn tournament_accept_owner(&self, tournament_id: TournamentId) -> Call<()> {
+        self.call("tournament_accept_owner", json!({ "tournament_id": tournament_id }))
+    }
+
+    pub fn propose_contract_owner(&self, new_owner_id: AccountId) -> Call<()> {
+        self.call("propose_contract_owner", json!({ "new_owner_id": new_owner_id }))
+    }
+
+    pub fn accept_contract_owner(&self) -> Call<()> {
+        self.call("accept_contract_owner", json!({}))
+    }
+
+    pub fn tournament_cancel_owner(&self, tournament_id: TournamentId) -> Call<()> {
+        self.call("tournament_cancel_owner", json!({ "tournament_id": tournament_id }))
+    }
+
+    pub fn cancel_contract_owner(&self) -> Call<()> {
+        self.call("cancel_contract_owner", json!({}))
+    }
+
+    /// The owner of the contract and the account the ownership was proposed to
+    pub fn contract_owner(&self) -> View<(AccountId, Option<AccountId>)> {
+        self.view("contract_owner", json!({}))
+    }
+
+    pub fn contract_metadata(&self) -> View<TournamentContractMetadata> {
+        self.view("contract_metadata", json!({}))
+    }
+
+    //pause
+
+    /// Pause the whole contract (no tournament) or the tournament
+    pub fn pause(&self, tournament_id: Option<TournamentId>) -> Call<()> {
+        self.call("pause", json!({ "tournament_id": tournament_id }))
+    }
+
+    /// Unpause the whole contract (no tournament) or the tournament
+    pub fn unpause(&self, tournament_id: Option<TournamentId>) -> Call<()> {
+        self.call("unpause", json!({ "tournament_id": tournament_id }))
+    }
+
+    pub fn is_paused(&self, tournament_id: Option<TournamentId>) -> View<bool> {
+        self.view("is_paused", json!({ "tournament_id": tournament_id }))
+    }
+
+    //upgrade
+
+    /// Nanoseconds between staging an upgrade and applying it
+    pub fn set_upgrade_delay(&self, delay: u64) -> Call<()> {
+        self.call("set_upgrade_delay", json!({ "delay": U64(delay) }))
+    }
+
+    pub fn upgrade_delay(&self) -> View<U64> {
+        self.view("upgrade_delay", json!({}))
+    }
+
+    /// Deploy the wasm code at once, the code is the raw argument of the call
+    pub fn upgrade(&self, code: Vec<u8>) -> Call<()> {
+        Call::new(&self.contract_id, "upgrade", code).with_gas(UPGRADE_GAS)
+    }
+
+    /// Stage the wasm code to be applied after the upgrade delay
+    pub fn stage_upgrade(&self, code: Vec<u8>) -> Call<()> {
+        Call::new(&self.contract_id, "stage_upgrade", code).with_gas(UPGRADE_GAS)
+    }
+
+    pub fn staged_upgrade(&self) -> View<Option<JsonStagedUpgrade>> {
+        self.view("staged_upgrade", json!({}))
+    }
+
+    pub fn cancel_upgrade(&self) -> Call<()> {
+        self.call("cancel_upgrade", json!({}))
+    }
+
+    pub fn apply_upgrade(&self) -> Call<()> {
+        self.call("apply_upgrade", json!({})).with_gas(UPGRADE_GAS)
+    }
+}
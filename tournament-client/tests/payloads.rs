@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+use near_sdk::AccountId;
+use near_sdk::json_types::{U64, U128};
+use serde_json::{json, Value};
+use tournament_client::*;
+
+const NEAR_0_1: u128 = 100_000_000_000_000_000_000_000;
+
+fn account(account_id: &str) -> AccountId {
+    account_id.parse().unwrap()
+}
+
+fn client() -> TournamentClient {
+    TournamentClient::new(account("tournament.testnet"))
+}
+
+fn args(args: &[u8]) -> Value {
+    serde_json::from_slice(args).unwrap()
+}
+
+#[test]
+fn tournament_create_payload() {
+    let mut basis_points_map = HashMap::new();
+    basis_points_map.insert(1, 5_000);
+    basis_points_map.insert(2, 3_000);
+
+    let call = client().tournament_create(
+        TournamentCreateArgs::new(
+            "tournament-1".to_string(),
+            "Tournament-1".to_string(),
+            8,
+            NEAR_0_1,
+            account("dgstai.testnet"),
+            basis_points_map,
+        )
+        .icon("https://example.com/icon.png".to_string())
+        .schedule(Some(1_000), None),
+    );
+
+    assert_eq!(call.contract_id(), &account("tournament.testnet"));
+    assert_eq!(call.method_name(), "tournament_create");
+    assert_eq!(call.deposit(), 0);
+    assert_eq!(call.gas(), DEFAULT_GAS);
+    assert_eq!(args(call.args()), json!({
+        "args": {
+            "tournament_id": "tournament-1",
+            "name": "Tournament-1",
+            "icon": "https://example.com/icon.png",
+            "starts_at": "1000",
+            "players_number": 8,
+            "in_price": "100000000000000000000000",
+            "tournament_owner_id": "dgstai.testnet",
+            "basis_points_map": { "1": 5000, "2": 3000 },
+        },
+    }));
+}
+
+#[test]
+fn guaranteed_tournament_create_payload() {
+    let call = client().tournament_create(
+        TournamentCreateArgs::new("tournament-2".to_string(), "Sunday million".to_string(), 100, NEAR_0_1, account("dgstai.testnet"), HashMap::new()),
+    ).with_deposit(50 * NEAR_0_1);
+
+    assert_eq!(call.method_name(), "tournament_create");
+    assert_eq!(call.deposit(), 50 * NEAR_0_1);
+}
+
+#[test]
+fn participate_tournament_attaches_deposit() {
+    let call = client().participate_tournament("tournament-1".to_string(), None, 2 * NEAR_0_1);
+
+    assert_eq!(call.method_name(), "participate_tournament");
+    assert_eq!(call.deposit(), 2 * NEAR_0_1);
+    assert_eq!(args(call.args()), json!({ "tournament_id": "tournament-1" }));
+}
+
+#[test]
+fn participate_tournament_for_payload() {
+    let call = client().participate_tournament_for("tournament-1".to_string(), account("alice.testnet"), None, NEAR_0_1);
+
+    assert_eq!(call.method_name(), "participate_tournament_for");
+    assert_eq!(call.deposit(), NEAR_0_1);
+    assert_eq!(args(call.args()), json!({ "tournament_id": "tournament-1", "player_id": "alice.testnet" }));
+}
+
+#[test]
+fn withdraw_sponsored_seat_payload() {
+    let call = client().withdraw_sponsored_seat("tournament-1".to_string(), account("alice.testnet"));
+
+    assert_eq!(call.method_name(), "withdraw_sponsored_seat");
+    assert_eq!(call.deposit(), 0);
+    assert_eq!(args(call.args()), json!({ "tournament_id": "tournament-1", "player_id": "alice.testnet" }));
+}
+
+#[test]
+fn register_team_payload() {
+    let call = client().register_team(
+        "tournament-1".to_string(),
+        "Blue".to_string(),
+        vec![account("alice.testnet"), account("bob.testnet")],
+        Some(vec![3, 1]),
+        None,
+        NEAR_0_1,
+    );
+
+    assert_eq!(call.method_name(), "register_team");
+    assert_eq!(call.deposit(), NEAR_0_1);
+    assert_eq!(args(call.args()), json!({
+        "tournament_id": "tournament-1",
+        "name": "Blue",
+        "members": ["alice.testnet", "bob.testnet"],
+        "shares": [3, 1],
+    }));
+
+    let create = client().tournament_create(
+        TournamentCreateArgs::new("tournament-2".to_string(), "Teams".to_string(), 4, NEAR_0_1, account("dgstai.testnet"), HashMap::new())
+            .team_size(5),
+    );
+    assert_eq!(args(create.args())["args"]["team_size"], json!(5));
+}
+
+#[test]
+fn access_payloads() {
+    let call = client().participate_tournament("tournament-1".to_string(), Some("secret".to_string()), NEAR_0_1);
+    assert_eq!(args(call.args()), json!({ "tournament_id": "tournament-1", "invite_code": "secret" }));
+
+    let call = client().add_invite_codes("tournament-1".to_string(), vec![vec![0; 32]], true);
+    assert_eq!(call.method_name(), "add_invite_codes");
+    assert_eq!(args(call.args()), json!({
+        "tournament_id": "tournament-1",
+        "code_hashes": ["AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="],
+        "single_use": true,
+    }));
+
+    let create = client().tournament_create(
+        TournamentCreateArgs::new("tournament-2".to_string(), "Private".to_string(), 4, NEAR_0_1, account("dgstai.testnet"), HashMap::new())
+            .access(TournamentAccess::Allowlist),
+    );
+    assert_eq!(args(create.args())["args"]["access"], json!("allowlist"));
+}
+
+#[test]
+fn gates_payloads() {
+    let create = client().tournament_create(
+        TournamentCreateArgs::new("tournament-2".to_string(), "Guild cup".to_string(), 4, NEAR_0_1, account("dgstai.testnet"), HashMap::new())
+            .gates(vec![
+                EntryGate::Nft { contract_id: account("guild-nft.testnet") },
+                EntryGate::Ft { contract_id: account("guild-ft.testnet"), min_balance: U128(1_000) },
+            ]),
+    );
+    assert_eq!(args(create.args())["args"]["gates"], json!([
+        { "nft": { "contract_id": "guild-nft.testnet" } },
+        { "ft": { "contract_id": "guild-ft.testnet", "min_balance": "1000" } },
+    ]));
+
+    let call = client().participate_tournament("tournament-2".to_string(), None, NEAR_0_1).with_gas(GATED_ENTRANCE_GAS);
+    assert_eq!(call.gas(), GATED_ENTRANCE_GAS);
+}
+
+#[test]
+fn reward_prizes_payload() {
+    let mut winners_map = HashMap::new();
+    winners_map.insert(1, account("alice.testnet"));
+    winners_map.insert(2, account("bob.testnet"));
+
+    let call = client().reward_prizes("tournament-1".to_string(), winners_map);
+
+    assert_eq!(call.gas(), PAYOUT_GAS);
+    assert_eq!(args(call.args()), json!({
+        "tournament_id": "tournament-1",
+        "winners_map": { "1": "alice.testnet", "2": "bob.testnet" },
+    }));
+}
+
+#[test]
+fn tournament_update_payload() {
+    let call = client().tournament_update("tournament-1".to_string(), TournamentMetadataUpdate {
+        name: Some("Final".to_string()),
+        in_price: Some(U128(NEAR_0_1)),
+        ..Default::default()
+    });
+
+    let payload = args(call.args());
+    assert_eq!(payload["tournament_id"], json!("tournament-1"));
+    assert_eq!(payload["update"]["name"], json!("Final"));
+    assert_eq!(payload["update"]["in_price"], json!("100000000000000000000000"));
+}
+
+#[test]
+fn display_tournaments_payload() {
+    let view = client().display_tournaments(
+        Some(10),
+        Some(5),
+        Some(TournamentFilter { state: Some(TournamentState::Open), ..Default::default() }),
+        None,
+    );
+
+    let payload = args(view.args());
+    assert_eq!(payload["from_index"], json!("10"));
+    assert_eq!(payload["limit"], json!(5));
+    assert_eq!(payload["filter"]["state"], json!("open"));
+    assert_eq!(payload["reverse"], Value::Null);
+}
+
+#[test]
+fn pause_payloads() {
+    assert_eq!(args(client().pause(None).args()), json!({ "tournament_id": null }));
+    assert_eq!(
+        args(client().unpause(Some("tournament-1".to_string())).args()),
+        json!({ "tournament_id": "tournament-1" }),
+    );
+}
+
+#[test]
+fn upgrade_passes_raw_code() {
+    let call = client().stage_upgrade(vec![0, 97, 115, 109]);
+
+    assert_eq!(call.args(), &[0, 97, 115, 109]);
+    assert_eq!(call.gas(), UPGRADE_GAS);
+}
+
+#[test]
+fn decode_results() {
+    let client = client();
+
+    assert_eq!(client.tournaments_total_supply().decode(br#""3""#).unwrap(), U128(3));
+    assert_eq!(client.display_freeplaces_in_tournament("tournament-1".to_string()).decode(br#""6""#).unwrap(), Some(U64(6)));
+    assert!(client.display_tournament("tournament-2".to_string()).decode(b"null").unwrap().is_none());
+    assert_eq!(
+        client.contract_owner().decode(br#"["tournament.testnet",null]"#).unwrap(),
+        (account("tournament.testnet"), None),
+    );
+    //calls without a return value return no bytes
+    client.withdraw_fees("tournament-1".to_string()).decode(b"").unwrap();
+
+    let tournament = client.display_tournament("tournament-1".to_string()).decode(br#"{
+        "tournament_id": "tournament-1",
+        "owner_id": "dgstai.testnet",
+        "pending_owner_id": null,
+        "metadata": {
+            "name": "Tournament-1",
+            "icon": null,
+            "description": null,
+            "game": null,
+            "reference": null,
+            "reference_hash": null,
+            "links": null,
+            "extra": null,
+            "starts_at": null,
+            "ends_at": null,
+            "players_number": 8,
+            "in_price": 100000000000000000000000
+        },
+        "first_place_prize": "5000",
+        "second_place_prize": "3000",
+        "third_place_prize": "0",
+        "active": true,
+        "state": "open",
+        "prize_fond": "200000000000000000000000",
+        "effective_prize_fond": "200000000000000000000000"
+    }"#).unwrap().unwrap();
+    assert_eq!(tournament.owner_id, account("dgstai.testnet"));
+    assert_eq!(tournament.metadata.in_price, NEAR_0_1);
+    assert_eq!(tournament.state, TournamentState::Open);
+    assert_eq!(tournament.prize_fond, U128(2 * NEAR_0_1));
+    assert!(tournament.sponsors.is_empty());
+    assert_eq!(tournament.guaranteed_prize_fond, None);
+}
+
+#[test]
+fn sponsor_tournament_payload() {
+    let call = client().sponsor_tournament("tournament-1".to_string(), 5 * NEAR_0_1);
+
+    assert_eq!(call.method_name(), "sponsor_tournament");
+    assert_eq!(call.deposit(), 5 * NEAR_0_1);
+    assert_eq!(args(call.args()), json!({ "tournament_id": "tournament-1" }));
+}
+
+#[test]
+fn set_payout_tiers_payload() {
+    let call = client().set_payout_tiers("tournament-1".to_string(), vec![PayoutTier {
+        min_entrants: 2,
+        max_entrants: 8,
+        basis_points_map: vec![(1, 7_000), (2, 3_000)].into_iter().collect(),
+    }]);
+
+    assert_eq!(call.method_name(), "set_payout_tiers");
+    assert_eq!(args(call.args()), json!({
+        "tournament_id": "tournament-1",
+        "payout_tiers": [{ "min_entrants": 2, "max_entrants": 8, "basis_points_map": { "1": 7000, "2": 3000 } }],
+    }));
+}
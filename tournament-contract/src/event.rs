@@ -1,23 +1,36 @@
 use near_sdk::env;
 use near_sdk::serde::{Serialize};
 
+/// Name of the events standard of the tournament contract
+pub const TOURNAMENT_STANDARD_NAME: &str = "tournament";
+
+/// Version of the events standard of the tournament contract
+pub const TOURNAMENT_SPEC_VERSION: &str = "2.0.0";
+
+/// Name the events standard had before `tournament`, still accepted by the event parser
+pub const LEGACY_STANDARD_NAME: &str = "mf1";
+
+/// Version of the events logged before 2.0.0, still accepted by the event parser
+pub const LEGACY_SPEC_VERSION: &str = "1.0.0";
+
+/// NEP-297 envelope of the events: `{"standard": ..., "version": ..., "event": ..., "data": [...]}`
 #[derive(Serialize, Debug)]
 #[serde(tag = "standard")]
 #[must_use = "don't forget to `.emit()` this event"]
 #[serde(rename_all = "snake_case")]
 #[serde(crate = "near_sdk::serde")]
 pub(crate) enum NearEvent<'a> {
-    Mf1(crate::tournament::events::EventLog<'a>),    
+    Tournament(crate::tournament::events::EventLog<'a>),    
 }
 
 impl<'a> NearEvent<'a> {
-    fn to_json_string(&self) -> String {
+    pub(crate) fn to_json_string(&self) -> String {
         // Events cannot fail to serialize so fine to panic on error
         #[allow(clippy::redundant_closure)]
         serde_json::to_string(self).ok().unwrap_or_else(|| env::abort())
     }
 
-    fn to_json_event_string(&self) -> String {
+    pub(crate) fn to_json_event_string(&self) -> String {
         format!("EVENT_JSON:{}", self.to_json_string())
     }
 
@@ -0,0 +1,527 @@
+//! Typed parsing of the `EVENT_JSON:` logs of the tournament contract for the indexers.
+//!
+//! The structs mirror the borrowed logs of `crate::tournament::events`, fields added in the
+//! version 2.0.0 are optional so the version 1.0.0 logs can be read as well. The contract
+//! doesn't log 1.0.0 anymore, the parser reads it for the logs of the old deployments.
+
+use std::fmt;
+use near_sdk::AccountId;
+use near_sdk::json_types::{U64, U128, Base64VecU8};
+use near_sdk::serde::{Deserialize, Deserializer, Serialize};
+use near_sdk::serde::de::{self, MapAccess, Visitor};
+pub use crate::event::{TOURNAMENT_STANDARD_NAME, TOURNAMENT_SPEC_VERSION, LEGACY_STANDARD_NAME, LEGACY_SPEC_VERSION};
+
+/// Prefix of the event logs
+pub const EVENT_JSON_PREFIX: &str = "EVENT_JSON:";
+
+/// An event logged by the tournament contract
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentEvent {
+    pub standard: String,
+    pub version: String,
+    #[serde(flatten)]
+    pub event: TournamentEventData,
+}
+
+/// Data of the event, one variant per event name
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[serde(crate = "near_sdk::serde")]
+pub enum TournamentEventData {
+    TournamentCreate(Vec<TournamentCreateData>),
+    TournamentEntrance(Vec<TournamentEntranceData>),
+    TournamentPrizesReward(Vec<TournamentPrizesRewardData>),
+    TournamentUpdate(Vec<TournamentUpdateData>),
+    TournamentOwnerPropose(Vec<TournamentOwnerChangeData>),
+    TournamentOwnerAccept(Vec<TournamentOwnerChangeData>),
+    ContractOwnerPropose(Vec<ContractOwnerChangeData>),
+    ContractOwnerAccept(Vec<ContractOwnerChangeData>),
+    TournamentOwnerCancel(Vec<TournamentOwnerChangeData>),
+    ContractOwnerCancel(Vec<ContractOwnerChangeData>),
+    TournamentCancel(Vec<TournamentCancelData>),
+    TournamentLeave(Vec<TournamentLeaveData>),
+    TournamentRefund(Vec<TournamentRefundData>),
+    TournamentFeesWithdraw(Vec<TournamentFeesWithdrawData>),
+    ContractUpgradeStage(Vec<ContractUpgradeStageData>),
+    ContractUpgradeCancel(Vec<ContractUpgradeData>),
+    ContractUpgrade(Vec<ContractUpgradeData>),
+    ContractPause(Vec<ContractPauseData>),
+    ContractUnpause(Vec<ContractPauseData>),
+    TournamentTeamRegister(Vec<TournamentTeamData>),
+    TournamentTeamUpdate(Vec<TournamentTeamData>),
+    TournamentAllowlistUpdate(Vec<TournamentAllowlistUpdateData>),
+    TournamentInviteCodesUpdate(Vec<TournamentInviteCodesUpdateData>),
+    TournamentEntryReject(Vec<TournamentEntryRejectData>),
+    TournamentSponsor(Vec<TournamentSponsorData>),
+    TournamentGuarantee(Vec<TournamentGuaranteeData>),
+    TournamentGuaranteeSettle(Vec<TournamentGuaranteeSettleData>),
+    TournamentPayoutTierLock(Vec<TournamentPayoutTierLockData>),
+}
+
+const EVENT_NAMES: &[&str] = &[
+    "tournament_create",
+    "tournament_entrance",
+    "tournament_prizes_reward",
+    "tournament_update",
+    "tournament_owner_propose",
+    "tournament_owner_accept",
+    "contract_owner_propose",
+    "contract_owner_accept",
+    "tournament_owner_cancel",
+    "contract_owner_cancel",
+    "tournament_cancel",
+    "tournament_leave",
+    "tournament_refund",
+    "tournament_fees_withdraw",
+    "contract_upgrade_stage",
+    "contract_upgrade_cancel",
+    "contract_upgrade",
+    "contract_pause",
+    "contract_unpause",
+    "tournament_team_register",
+    "tournament_team_update",
+    "tournament_allowlist_update",
+    "tournament_invite_codes_update",
+    "tournament_entry_reject",
+    "tournament_sponsor",
+    "tournament_guarantee",
+    "tournament_guarantee_settle",
+    "tournament_payout_tier_lock",
+];
+
+impl TournamentEventData {
+    //read the data of the named event from the current map value
+    fn next_data<'de, A: MapAccess<'de>>(event: &str, version: &str, map: &mut A) -> Result<Self, A::Error> {
+        Ok(match event {
+            //the version 1.0.0 logged the rewarded amount as a JSON number
+            "tournament_prizes_reward" if version == LEGACY_SPEC_VERSION => TournamentEventData::TournamentPrizesReward(
+                map.next_value::<Vec<TournamentPrizesRewardDataV1>>()?.into_iter().map(Into::into).collect()
+            ),
+            "tournament_create" => TournamentEventData::TournamentCreate(map.next_value()?),
+            "tournament_entrance" => TournamentEventData::TournamentEntrance(map.next_value()?),
+            "tournament_prizes_reward" => TournamentEventData::TournamentPrizesReward(map.next_value()?),
+            "tournament_update" => TournamentEventData::TournamentUpdate(map.next_value()?),
+            "tournament_owner_propose" => TournamentEventData::TournamentOwnerPropose(map.next_value()?),
+            "tournament_owner_accept" => TournamentEventData::TournamentOwnerAccept(map.next_value()?),
+            "contract_owner_propose" => TournamentEventData::ContractOwnerPropose(map.next_value()?),
+            "contract_owner_accept" => TournamentEventData::ContractOwnerAccept(map.next_value()?),
+            "tournament_owner_cancel" => TournamentEventData::TournamentOwnerCancel(map.next_value()?),
+            "contract_owner_cancel" => TournamentEventData::ContractOwnerCancel(map.next_value()?),
+            "tournament_cancel" => TournamentEventData::TournamentCancel(map.next_value()?),
+            "tournament_leave" => TournamentEventData::TournamentLeave(map.next_value()?),
+            "tournament_refund" => TournamentEventData::TournamentRefund(map.next_value()?),
+            "tournament_fees_withdraw" => TournamentEventData::TournamentFeesWithdraw(map.next_value()?),
+            "contract_upgrade_stage" => TournamentEventData::ContractUpgradeStage(map.next_value()?),
+            "contract_upgrade_cancel" => TournamentEventData::ContractUpgradeCancel(map.next_value()?),
+            "contract_upgrade" => TournamentEventData::ContractUpgrade(map.next_value()?),
+            "contract_pause" => TournamentEventData::ContractPause(map.next_value()?),
+            "contract_unpause" => TournamentEventData::ContractUnpause(map.next_value()?),
+            "tournament_team_register" => TournamentEventData::TournamentTeamRegister(map.next_value()?),
+            "tournament_team_update" => TournamentEventData::TournamentTeamUpdate(map.next_value()?),
+            "tournament_allowlist_update" => TournamentEventData::TournamentAllowlistUpdate(map.next_value()?),
+            "tournament_invite_codes_update" => TournamentEventData::TournamentInviteCodesUpdate(map.next_value()?),
+            "tournament_entry_reject" => TournamentEventData::TournamentEntryReject(map.next_value()?),
+            "tournament_sponsor" => TournamentEventData::TournamentSponsor(map.next_value()?),
+            "tournament_guarantee" => TournamentEventData::TournamentGuarantee(map.next_value()?),
+            "tournament_guarantee_settle" => TournamentEventData::TournamentGuaranteeSettle(map.next_value()?),
+            "tournament_payout_tier_lock" => TournamentEventData::TournamentPayoutTierLock(map.next_value()?),
+            other => return Err(de::Error::unknown_variant(other, EVENT_NAMES)),
+        })
+    }
+}
+
+//The event is read without buffering (unlike `#[serde(flatten)]`), so the yoctoNEAR amounts
+//logged as JSON numbers by the version 1.0.0 keep their u128 precision. `version` and `event`
+//have to go before `data`, as the contract logs them.
+impl<'de> Deserialize<'de> for TournamentEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EventVisitor;
+
+        impl<'de> Visitor<'de> for EventVisitor {
+            type Value = TournamentEvent;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a tournament event object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut standard: Option<String> = None;
+                let mut version: Option<String> = None;
+                let mut event: Option<String> = None;
+                let mut data: Option<TournamentEventData> = None;
+
+                while let Some(key) = map.next_key::<String>()? {
+                    match key.as_str() {
+                        "standard" => standard = Some(map.next_value()?),
+                        "version" => version = Some(map.next_value()?),
+                        "event" => event = Some(map.next_value()?),
+                        "data" => {
+                            let event = event.as_deref().ok_or_else(|| de::Error::custom("`event` should go before `data`"))?;
+                            let version = version.as_deref().ok_or_else(|| de::Error::custom("`version` should go before `data`"))?;
+                            data = Some(TournamentEventData::next_data(event, version, &mut map)?);
+                        }
+                        _ => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                Ok(TournamentEvent {
+                    standard: standard.ok_or_else(|| de::Error::missing_field("standard"))?,
+                    version: version.ok_or_else(|| de::Error::missing_field("version"))?,
+                    event: data.ok_or_else(|| de::Error::missing_field("data"))?,
+                })
+            }
+        }
+
+        deserializer.deserialize_map(EventVisitor)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentCreateData {
+    pub tournament_id: String,
+    //added in 2.0.0
+    #[serde(default)]
+    pub owner_id: Option<AccountId>,
+    pub players_number: u32,
+    pub in_price: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentEntranceData {
+    pub partisipator_id: AccountId,
+    pub tournament_id: String,
+    //the account that paid for the player, if it isn't the player
+    #[serde(default)]
+    pub payer_id: Option<AccountId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentPrizesRewardData {
+    pub tournament_id: String,
+    pub rewarded_amount: U128,
+    //added in 2.0.0
+    #[serde(default)]
+    pub winners: Vec<TournamentWinnerData>,
+}
+
+//the prizes reward of the version 1.0.0, the rewarded amount was a JSON number
+#[derive(Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+struct TournamentPrizesRewardDataV1 {
+    tournament_id: String,
+    rewarded_amount: u128,
+}
+
+impl From<TournamentPrizesRewardDataV1> for TournamentPrizesRewardData {
+    fn from(data: TournamentPrizesRewardDataV1) -> Self {
+        TournamentPrizesRewardData {
+            tournament_id: data.tournament_id,
+            rewarded_amount: U128(data.rewarded_amount),
+            winners: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentWinnerData {
+    pub place: u32,
+    pub account_id: AccountId,
+    pub amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentUpdateData {
+    pub tournament_id: String,
+    pub updated_fields: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentOwnerChangeData {
+    pub tournament_id: String,
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractOwnerChangeData {
+    pub old_owner_id: AccountId,
+    pub new_owner_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentCancelData {
+    pub tournament_id: String,
+    pub refunded_amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentLeaveData {
+    pub tournament_id: String,
+    pub player_id: AccountId,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentRefundData {
+    pub tournament_id: String,
+    pub account_id: AccountId,
+    pub amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentFeesWithdrawData {
+    pub tournament_id: String,
+    pub owner_id: AccountId,
+    pub amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractUpgradeStageData {
+    pub code_hash: Base64VecU8,
+    pub apply_after: U64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractUpgradeData {
+    pub code_hash: Base64VecU8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractPauseData {
+    //the whole contract when there is no tournament
+    #[serde(default)]
+    pub tournament_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentTeamData {
+    pub tournament_id: String,
+    pub captain_id: AccountId,
+    pub name: String,
+    pub members: Vec<AccountId>,
+    pub shares: Vec<u16>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentAllowlistUpdateData {
+    pub tournament_id: String,
+    pub added: Vec<AccountId>,
+    pub removed: Vec<AccountId>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentInviteCodesUpdateData {
+    pub tournament_id: String,
+    pub added: Vec<Base64VecU8>,
+    pub removed: Vec<Base64VecU8>,
+    //only logged when codes are added
+    #[serde(default)]
+    pub single_use: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentEntryRejectData {
+    pub tournament_id: String,
+    pub player_id: AccountId,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentSponsorData {
+    pub tournament_id: String,
+    pub sponsor_id: AccountId,
+    pub amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentGuaranteeData {
+    pub tournament_id: String,
+    pub owner_id: AccountId,
+    pub amount: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentGuaranteeSettleData {
+    pub tournament_id: String,
+    pub owner_id: AccountId,
+    pub covered: U128,
+    pub returned: U128,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentPayoutTierLockData {
+    pub tournament_id: String,
+    pub entrants: u32,
+    pub tier: Option<u8>,
+}
+
+/// Reasons a log line can't be parsed as a tournament event
+#[derive(Debug)]
+pub enum EventParseError {
+    /// The line doesn't start with `EVENT_JSON:`
+    NotAnEvent,
+    /// The event belongs to another standard (e.g. nep171 of an NFT contract)
+    UnknownStandard(String),
+    /// The JSON doesn't match the schema of the events
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for EventParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventParseError::NotAnEvent => write!(f, "log is not an {} line", EVENT_JSON_PREFIX),
+            EventParseError::UnknownStandard(standard) => write!(f, "unknown event standard: {}", standard),
+            EventParseError::Json(error) => write!(f, "invalid event JSON: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for EventParseError {}
+
+impl From<serde_json::Error> for EventParseError {
+    fn from(error: serde_json::Error) -> Self {
+        EventParseError::Json(error)
+    }
+}
+
+/// Parses a log line emitted by the tournament contract
+pub fn parse_event_log(log: &str) -> Result<TournamentEvent, EventParseError> {
+    let json = log.strip_prefix(EVENT_JSON_PREFIX).ok_or(EventParseError::NotAnEvent)?;
+
+    //check the standard first to tell the events of other contracts from the invalid ones
+    #[derive(Deserialize)]
+    #[serde(crate = "near_sdk::serde")]
+    struct Envelope {
+        standard: String,
+    }
+    let envelope: Envelope = serde_json::from_str(json)?;
+    if envelope.standard != TOURNAMENT_STANDARD_NAME && envelope.standard != LEGACY_STANDARD_NAME {
+        return Err(EventParseError::UnknownStandard(envelope.standard));
+    }
+
+    Ok(serde_json::from_str(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tournament::events::*;
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    fn last_event() -> TournamentEvent {
+        let logs = get_logs();
+        parse_event_log(logs.last().expect("No logs")).unwrap()
+    }
+
+    #[test]
+    fn round_trip_create() {
+        testing_env!(VMContextBuilder::new().build());
+        let tournament_id = "tournament-1".to_string();
+        TournamentCreateLog {
+            tournament_id: &tournament_id,
+            owner_id: &accounts(0),
+            players_number: &8,
+            in_price: &U128(100),
+        }.emit();
+
+        let event = last_event();
+        assert_eq!(event.standard, TOURNAMENT_STANDARD_NAME);
+        assert_eq!(event.version, TOURNAMENT_SPEC_VERSION);
+        assert_eq!(event.event, TournamentEventData::TournamentCreate(vec![TournamentCreateData {
+            tournament_id,
+            owner_id: Some(accounts(0)),
+            players_number: 8,
+            in_price: U128(100),
+        }]));
+    }
+
+    #[test]
+    fn round_trip_prizes_reward() {
+        testing_env!(VMContextBuilder::new().build());
+        let tournament_id = "tournament-1".to_string();
+        TournamentPrizesRewardLog {
+            tournament_id: &tournament_id,
+            rewarded_amount: &U128(80),
+            winners: &[
+                TournamentWinnerLog { place: 1, account_id: accounts(1), amount: U128(50) },
+                TournamentWinnerLog { place: 2, account_id: accounts(2), amount: U128(30) },
+            ],
+        }.emit();
+
+        assert_eq!(last_event().event, TournamentEventData::TournamentPrizesReward(vec![TournamentPrizesRewardData {
+            tournament_id,
+            rewarded_amount: U128(80),
+            winners: vec![
+                TournamentWinnerData { place: 1, account_id: accounts(1), amount: U128(50) },
+                TournamentWinnerData { place: 2, account_id: accounts(2), amount: U128(30) },
+            ],
+        }]));
+    }
+
+    #[test]
+    fn round_trip_many_refunds() {
+        testing_env!(VMContextBuilder::new().build());
+        let tournament_id = "tournament-1".to_string();
+        TournamentRefundLog::emit_many(&[
+            TournamentRefundLog { tournament_id: &tournament_id, account_id: &accounts(1), amount: &U128(10) },
+            TournamentRefundLog { tournament_id: &tournament_id, account_id: &accounts(2), amount: &U128(10) },
+        ]);
+
+        match last_event().event {
+            TournamentEventData::TournamentRefund(refunds) => {
+                assert_eq!(refunds.len(), 2);
+                assert_eq!(refunds[1].account_id, accounts(2));
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn parse_legacy_v1() {
+        let event = parse_event_log(
+            r#"EVENT_JSON:{"standard":"mf1","version":"1.0.0","event":"tournament_prizes_reward","data":[{"tournament_id":"tournament-1","rewarded_amount":90}]}"#
+        ).unwrap();
+
+        assert_eq!(event.standard, LEGACY_STANDARD_NAME);
+        assert_eq!(event.event, TournamentEventData::TournamentPrizesReward(vec![TournamentPrizesRewardData {
+            tournament_id: "tournament-1".to_string(),
+            rewarded_amount: U128(90),
+            winners: vec![],
+        }]));
+    }
+
+    #[test]
+    fn reject_foreign_logs() {
+        assert!(matches!(parse_event_log("Transfer 10 to alice"), Err(EventParseError::NotAnEvent)));
+        assert!(matches!(
+            parse_event_log(r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[]}"#),
+            Err(EventParseError::UnknownStandard(_))
+        ));
+    }
+}
@@ -1,19 +1,29 @@
+extern crate alloc;
+
 use std::collections::HashMap;
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::{LazyOption, LookupMap, UnorderedSet};
-use near_sdk::json_types::{U128, U64};
+use near_sdk::json_types::{U128, U64, Base64VecU8};
 use near_sdk::{
     env, near_bindgen, AccountId,  CryptoHash, PanicOnDefault,  BorshStorageKey
 };
-mod tournament;
+pub mod tournament;
 use crate::tournament::*;
+use crate::tournament::events::{ContractOwnerProposeLog, ContractOwnerAcceptLog, ContractOwnerCancelLog};
 mod macros;
 mod event;
+pub mod event_parser;
+pub mod payout;
+mod migration;
+pub mod upgrade;
+mod pause;
 
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {    
     owner_id: AccountId,
+    //the account the owner proposed to hand the contract over to
+    pending_owner_id: Option<AccountId>,
     tournament: TournamentContract,
     metadata: LazyOption<TournamentContractMetadata>, 
 }
@@ -28,6 +38,26 @@ pub enum StorageKey {
     TournamentMetadataById,
     TournamentContractMetadata,   
     PrizesPerTournamentInner { tournament_id_hash: CryptoHash },   
+    TournamentsPerOwner,
+    TournamentsPerOwnerInner { account_id_hash: CryptoHash },
+    TournamentsPerState,
+    TournamentsPerStateInner { state: TournamentState },
+    PausedTournaments,
+    TeamsPerTournament,
+    TeamsPerTournamentInner { tournament_id_hash: CryptoHash },
+    TeamMembersPerTournament,
+    TeamMembersPerTournamentInner { tournament_id_hash: CryptoHash },
+    PayersPerTournament,
+    PayersPerTournamentInner { tournament_id_hash: CryptoHash },
+    AllowedPerTournament,
+    AllowedPerTournamentInner { tournament_id_hash: CryptoHash },
+    InviteCodesPerTournament,
+    InviteCodesPerTournamentInner { tournament_id_hash: CryptoHash },
+    SponsorsPerTournament,
+    SponsorsPerTournamentInner { tournament_id_hash: CryptoHash },
+    GuaranteesPerTournament,
+    PayoutTiersPerTournament,
+    LockedPayoutTiersPerTournament,
 }
 
 #[near_bindgen]
@@ -61,19 +91,104 @@ impl Contract {
                 Some(&metadata),
         );
         
-        let tournament = TournamentContract::new(            
-            StorageKey::PlayersPerTournament,            
-            StorageKey::WinnersPercentPerTournament,           
-            StorageKey::TournamentsById,            
-            StorageKey::TournamentMetadataById,
-        );
+        let tournament = TournamentContract::new();
+        
+        //the fresh state has the current layout
+        migration::write_state_version(migration::STATE_VERSION);
         
         Self {
             owner_id: owner_id.clone(),
+            pending_owner_id: None,
             tournament,
             metadata,
         }
     }        
+    
+    /*
+        migration function called after the new code is deployed.
+        this reads the state in the layout of the previous versions of the contract
+        and converts it step by step into the current layout.
+    */
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let mut version = migration::read_state_version();
+        assert!(version <= migration::STATE_VERSION, "State version {} is newer than the code", version);
+        
+        //each step reads the stored state of its version and writes the next one
+        while version < migration::STATE_VERSION {
+            version = migration::migrate_page(u32::MAX);
+        }
+        
+        env::state_read().expect("No state to migrate")
+    }
+    
+    /*
+        migrates the state too big for one `migrate` call, up to `limit` items at a time.
+        called after the new code is deployed without `migrate` until it returns true,
+        `migrate` loads the migrated state after it.
+    */
+    #[private]
+    pub fn migrate_page(limit: u32) -> bool {
+        migration::migrate_page(limit) == migration::STATE_VERSION
+    }
+    
+    //check the contract owner calls the method
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(env::predecessor_account_id(), self.owner_id, "Owner's method");
+    }
+    
+    //propose a new owner for the contract (the proposed account has to accept it)
+    pub fn propose_contract_owner(&mut self, new_owner_id: AccountId) {
+        //check the owner calls this method
+        self.assert_owner();
+        
+        //the contract can't be handed over to the current owner
+        assert_ne!(new_owner_id, self.owner_id, "Already the owner of the contract");
+        
+        //save the proposal, a new proposal replaces the previous one
+        self.pending_owner_id = Some(new_owner_id.clone());
+        
+        ContractOwnerProposeLog{
+            old_owner_id: &self.owner_id,
+            new_owner_id: &new_owner_id,
+        }.emit();
+    }
+    
+    //accept the ownership of the contract proposed by the current owner
+    pub fn accept_contract_owner(&mut self) {
+        let account_id = env::predecessor_account_id();
+        
+        //check the proposed owner calls this method
+        assert_eq!(self.pending_owner_id.as_ref(), Some(&account_id), "Ownership wasn't proposed to the caller");
+        
+        //hand the contract over to the new owner
+        let old_owner_id = std::mem::replace(&mut self.owner_id, account_id);
+        self.pending_owner_id = None;
+        
+        ContractOwnerAcceptLog{
+            old_owner_id: &old_owner_id,
+            new_owner_id: &self.owner_id,
+        }.emit();
+    }
+    
+    //withdraw the pending ownership proposal of the contract
+    pub fn cancel_contract_owner(&mut self) {
+        //check the owner calls this method
+        self.assert_owner();
+        
+        let pending_owner_id = self.pending_owner_id.take().expect("No ownership proposal");
+        
+        ContractOwnerCancelLog{
+            old_owner_id: &self.owner_id,
+            new_owner_id: &pending_owner_id,
+        }.emit();
+    }
+    
+    //get the owner of the contract and the account the ownership was proposed to
+    pub fn contract_owner(&self) -> (AccountId, Option<AccountId>) {
+        (self.owner_id.clone(), self.pending_owner_id.clone())
+    }
 }
 
 pub trait ContractMetadata {
@@ -90,3 +205,96 @@ impl ContractMetadata for Contract {
 
 impl_tournament_contract_core!(Contract, tournament);
 impl_tournament_contract_enumeration!(Contract, tournament);
+impl_tournament_contract_ownership!(Contract, tournament);
+impl_tournament_contract_teams!(Contract, tournament);
+impl_tournament_contract_access!(Contract, tournament);
+impl_tournament_contract_gates!(Contract, tournament);
+impl_tournament_contract_sponsors!(Contract, tournament);
+impl_tournament_contract_payout_tiers!(Contract, tournament);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_parser::*;
+    use crate::tournament::testing::{logged_events, set_context};
+    use near_sdk::test_utils::accounts;
+
+    fn setup() -> Contract {
+        set_context(accounts(0), 0);
+        Contract::new_default_meta(accounts(0))
+    }
+
+    #[test]
+    fn propose_and_accept_contract_owner() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.propose_contract_owner(accounts(1));
+        assert_eq!(contract.contract_owner(), (accounts(0), Some(accounts(1))));
+        assert_eq!(logged_events(), vec![TournamentEventData::ContractOwnerPropose(vec![ContractOwnerChangeData {
+            old_owner_id: accounts(0),
+            new_owner_id: accounts(1),
+        }])]);
+
+        set_context(accounts(1), 0);
+        contract.accept_contract_owner();
+        assert_eq!(contract.contract_owner(), (accounts(1), None));
+        assert_eq!(logged_events(), vec![TournamentEventData::ContractOwnerAccept(vec![ContractOwnerChangeData {
+            old_owner_id: accounts(0),
+            new_owner_id: accounts(1),
+        }])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner's method")]
+    fn propose_contract_owner_by_non_owner() {
+        let mut contract = setup();
+
+        set_context(accounts(1), 0);
+        contract.propose_contract_owner(accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Already the owner of the contract")]
+    fn propose_contract_owner_to_owner() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.propose_contract_owner(accounts(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Ownership wasn't proposed to the caller")]
+    fn accept_contract_owner_by_wrong_account() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.propose_contract_owner(accounts(1));
+
+        set_context(accounts(2), 0);
+        contract.accept_contract_owner();
+    }
+
+    #[test]
+    fn cancel_contract_owner_proposal() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.propose_contract_owner(accounts(1));
+        contract.cancel_contract_owner();
+        assert_eq!(contract.contract_owner(), (accounts(0), None));
+        assert_eq!(logged_events().pop(), Some(TournamentEventData::ContractOwnerCancel(vec![ContractOwnerChangeData {
+            old_owner_id: accounts(0),
+            new_owner_id: accounts(1),
+        }])));
+    }
+
+    #[test]
+    #[should_panic(expected = "No ownership proposal")]
+    fn cancel_contract_owner_without_proposal() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.cancel_contract_owner();
+    }
+}
@@ -7,17 +7,20 @@ macro_rules! impl_tournament_contract_core {
         
         #[near_bindgen]
         impl TournamentContractCore for $contract {
+            #[payable]
             fn tournament_create(
+                &mut self,
+                args: TournamentCreateArgs
+            ) {
+                self.$tournament.tournament_create(args)
+            }
+            
+            fn tournament_update(
                 &mut self,
                 tournament_id: TournamentId,
-                name: String,
-                icon: Option<String>,
-                players_number: u8,
-                in_price: U128,        
-                tournament_owner_id: AccountId,
-                percents_map: HashMap<u8,u8>,
+                update: TournamentMetadataUpdate
             ) {
-                self.$tournament.tournament_create(tournament_id, name, icon, players_number, in_price, tournament_owner_id, percents_map)
+                self.$tournament.tournament_update(tournament_id, update)
             }
         
             fn display_tournament(
@@ -30,11 +33,30 @@ macro_rules! impl_tournament_contract_core {
             #[payable]
             fn participate_tournament(
                 &mut self,
-                tournament_id: TournamentId
+                tournament_id: TournamentId,
+                invite_code: Option<String>
             ) {
-                self.$tournament.participate_tournament(tournament_id)
+                self.$tournament.participate_tournament(tournament_id, invite_code)
             }    
             
+            #[payable]
+            fn participate_tournament_for(
+                &mut self,
+                tournament_id: TournamentId,
+                player_id: AccountId,
+                invite_code: Option<String>
+            ) {
+                self.$tournament.participate_tournament_for(tournament_id, player_id, invite_code)
+            }
+            
+            fn display_payer(
+                &self,
+                tournament_id: TournamentId,
+                player_id: AccountId
+            ) -> Option<AccountId> {
+                self.$tournament.display_payer(tournament_id, player_id)
+            }
+            
             fn display_freeplaces_in_tournament(
                 &self,
                 tournament_id: TournamentId
@@ -45,10 +67,47 @@ macro_rules! impl_tournament_contract_core {
             fn reward_prizes(
                 &mut self, 
                 tournament_id: TournamentId, 
-                winners_map: HashMap<u8,AccountId>
+                winners_map: HashMap<u32,AccountId>
             ) {
                 self.$tournament.reward_prizes(tournament_id, winners_map)
             }
+            
+            fn preview_rewards(
+                &self,
+                tournament_id: TournamentId,
+                winners_map: HashMap<u32,AccountId>
+            ) -> JsonRewardsPreview {
+                self.$tournament.preview_rewards(tournament_id, winners_map)
+            }
+            
+            fn cancel_tournament(
+                &mut self,
+                tournament_id: TournamentId
+            ) {
+                self.$tournament.cancel_tournament(tournament_id)
+            }
+            
+            fn leave_tournament(
+                &mut self,
+                tournament_id: TournamentId
+            ) {
+                self.$tournament.leave_tournament(tournament_id)
+            }
+            
+            fn withdraw_sponsored_seat(
+                &mut self,
+                tournament_id: TournamentId,
+                player_id: AccountId
+            ) {
+                self.$tournament.withdraw_sponsored_seat(tournament_id, player_id)
+            }
+            
+            fn withdraw_fees(
+                &mut self,
+                tournament_id: TournamentId
+            ) {
+                self.$tournament.withdraw_fees(tournament_id)
+            }
         }
     };
 }
@@ -66,10 +125,267 @@ macro_rules! impl_tournament_contract_enumeration {
             fn display_tournaments(
                 &self, 
                 from_index: Option<U128>,
-                limit: Option<u64>
+                limit: Option<u64>,
+                filter: Option<TournamentFilter>,
+                reverse: Option<bool>
             ) -> Vec<JsonTournament> {
-                self.$tournament.display_tournaments(from_index, limit)
+                self.$tournament.display_tournaments(from_index, limit, filter, reverse)
             }   
+            
+            fn tournaments_total_supply(&self) -> U128 {
+                self.$tournament.tournaments_total_supply()
+            }
+            
+            fn tournament_stats(&self) -> JsonTournamentStats {
+                self.$tournament.tournament_stats()
+            }
+            
+            fn tournament_supply_for_owner(
+                &self,
+                account_id: AccountId
+            ) -> U128 {
+                self.$tournament.tournament_supply_for_owner(account_id)
+            }
+            
+            fn tournaments_for_owner(
+                &self,
+                account_id: AccountId,
+                from_index: Option<U128>,
+                limit: Option<u64>
+            ) -> Vec<JsonTournament> {
+                self.$tournament.tournaments_for_owner(account_id, from_index, limit)
+            }
+            
+            fn tournament_supply_for_state(
+                &self,
+                state: TournamentState
+            ) -> U128 {
+                self.$tournament.tournament_supply_for_state(state)
+            }
+            
+            fn tournaments_for_state(
+                &self,
+                state: TournamentState,
+                from_index: Option<U128>,
+                limit: Option<u64>
+            ) -> Vec<JsonTournament> {
+                self.$tournament.tournaments_for_state(state, from_index, limit)
+            }
+            
+            fn tournament_players(
+                &self,
+                tournament_id: TournamentId,
+                from_index: Option<U128>,
+                limit: Option<u64>
+            ) -> Vec<AccountId> {
+                self.$tournament.tournament_players(tournament_id, from_index, limit)
+            }
+        }
+    };
+}
+
+/// Tournament ownership adds the two-step handover of a tournament
+/// to a new owner.
+#[macro_export]
+macro_rules! impl_tournament_contract_ownership {
+    ($contract: ident, $tournament: ident) => {
+        use $crate::tournament::ownership::TournamentContractOwnership;
+
+        #[near_bindgen]
+        impl TournamentContractOwnership for $contract {
+
+            fn tournament_propose_owner(
+                &mut self,
+                tournament_id: TournamentId,
+                new_owner_id: AccountId
+            ) {
+                self.$tournament.tournament_propose_owner(tournament_id, new_owner_id)
+            }
+
+            fn tournament_accept_owner(
+                &mut self,
+                tournament_id: TournamentId
+            ) {
+                self.$tournament.tournament_accept_owner(tournament_id)
+            }
+
+            fn tournament_cancel_owner(
+                &mut self,
+                tournament_id: TournamentId
+            ) {
+                self.$tournament.tournament_cancel_owner(tournament_id)
+            }
+        }
+    };
+}
+
+/// Tournament teams let captains register rosters taking one seat
+/// per team.
+#[macro_export]
+macro_rules! impl_tournament_contract_teams {
+    ($contract: ident, $tournament: ident) => {
+        use $crate::tournament::teams::TournamentContractTeams;
+
+        #[near_bindgen]
+        impl TournamentContractTeams for $contract {
+
+            #[payable]
+            fn register_team(
+                &mut self,
+                tournament_id: TournamentId,
+                name: String,
+                members: Vec<AccountId>,
+                shares: Option<Vec<u16>>,
+                invite_code: Option<String>
+            ) {
+                self.$tournament.register_team(tournament_id, name, members, shares, invite_code)
+            }
+
+            fn update_team(
+                &mut self,
+                tournament_id: TournamentId,
+                name: Option<String>,
+                members: Option<Vec<AccountId>>,
+                shares: Option<Vec<u16>>
+            ) {
+                self.$tournament.update_team(tournament_id, name, members, shares)
+            }
+
+            fn display_team(
+                &self,
+                tournament_id: TournamentId,
+                captain_id: AccountId
+            ) -> Option<JsonTeam> {
+                self.$tournament.display_team(tournament_id, captain_id)
+            }
+        }
+    };
+}
+
+/// Tournament access lets owners manage the allowlists and the invite
+/// codes of their tournaments.
+#[macro_export]
+macro_rules! impl_tournament_contract_access {
+    ($contract: ident, $tournament: ident) => {
+        use $crate::tournament::access::TournamentContractAccess;
+
+        #[near_bindgen]
+        impl TournamentContractAccess for $contract {
+
+            fn allowlist_add(
+                &mut self,
+                tournament_id: TournamentId,
+                account_ids: Vec<AccountId>
+            ) {
+                self.$tournament.allowlist_add(tournament_id, account_ids)
+            }
+
+            fn allowlist_remove(
+                &mut self,
+                tournament_id: TournamentId,
+                account_ids: Vec<AccountId>
+            ) {
+                self.$tournament.allowlist_remove(tournament_id, account_ids)
+            }
+
+            fn is_allowed(
+                &self,
+                tournament_id: TournamentId,
+                account_id: AccountId
+            ) -> bool {
+                self.$tournament.is_allowed(tournament_id, account_id)
+            }
+
+            fn add_invite_codes(
+                &mut self,
+                tournament_id: TournamentId,
+                code_hashes: Vec<Base64VecU8>,
+                single_use: Option<bool>
+            ) {
+                self.$tournament.add_invite_codes(tournament_id, code_hashes, single_use)
+            }
+
+            fn remove_invite_codes(
+                &mut self,
+                tournament_id: TournamentId,
+                code_hashes: Vec<Base64VecU8>
+            ) {
+                self.$tournament.remove_invite_codes(tournament_id, code_hashes)
+            }
+
+            fn is_invite_code_valid(
+                &self,
+                tournament_id: TournamentId,
+                invite_code: String
+            ) -> bool {
+                self.$tournament.is_invite_code_valid(tournament_id, invite_code)
+            }
+        }
+    };
+}
+
+/// Tournament gates finish the entrances of the gated tournaments once
+/// the gate contracts are checked.
+#[macro_export]
+macro_rules! impl_tournament_contract_gates {
+    ($contract: ident, $tournament: ident) => {
+        use $crate::tournament::gates::TournamentContractGates;
+
+        #[near_bindgen]
+        impl TournamentContractGates for $contract {
+
+            #[private]
+            fn on_entry_gates_checked(
+                &mut self,
+                tournament_id: TournamentId,
+                player_id: AccountId,
+                payer_id: AccountId,
+                deposit: U128,
+                invite_code: Option<String>,
+                gates: Vec<EntryGate>
+            ) -> bool {
+                self.$tournament.on_entry_gates_checked(tournament_id, player_id, payer_id, deposit, invite_code, gates)
+            }
+        }
+    };
+}
+
+/// Tournament sponsors add to the prize pools of the tournaments.
+#[macro_export]
+macro_rules! impl_tournament_contract_sponsors {
+    ($contract: ident, $tournament: ident) => {
+        use $crate::tournament::sponsors::TournamentContractSponsors;
+
+        #[near_bindgen]
+        impl TournamentContractSponsors for $contract {
+
+            #[payable]
+            fn sponsor_tournament(
+                &mut self,
+                tournament_id: TournamentId
+            ) {
+                self.$tournament.sponsor_tournament(tournament_id)
+            }
+        }
+    };
+}
+
+/// Tournament payout tiers scale the prizes with the number of entrants.
+#[macro_export]
+macro_rules! impl_tournament_contract_payout_tiers {
+    ($contract: ident, $tournament: ident) => {
+        use $crate::tournament::tiers::TournamentContractPayoutTiers;
+
+        #[near_bindgen]
+        impl TournamentContractPayoutTiers for $contract {
+
+            fn set_payout_tiers(
+                &mut self,
+                tournament_id: TournamentId,
+                payout_tiers: Vec<PayoutTier>
+            ) {
+                self.$tournament.set_payout_tiers(tournament_id, payout_tiers)
+            }
         }
     };
 }
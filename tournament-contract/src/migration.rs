@@ -0,0 +1,553 @@
+//! Versioned layouts of the contract state and the migrations between them.
+//!
+//! The state version is kept under its own storage key, the state written before
+//! the versioning was introduced has no such key and is the version 1.
+//! Each step of `MIGRATIONS` reads the stored state of its version and writes the next one,
+//! `Contract::migrate` runs them one after another up to `STATE_VERSION`.
+//! A released step is frozen: it reads and writes only the layouts of this module,
+//! so the later changes of the contract types don't change what it does.
+//!
+//! A step migrates its state in pages of up to the given number of items and keeps its progress
+//! in the storage until the whole state is migrated. A tournament of the version 1 with prizes for
+//! all its 255 places takes about 90 TGas, so a page of up to 2 tournaments fits in the gas of a call.
+//! A bigger state is migrated with `Contract::migrate_page` before `Contract::migrate` is called.
+
+use crate::*;
+use near_sdk::Balance;
+use near_sdk::collections::{LookupSet, UnorderedMap};
+use std::ops::Range;
+
+/// Steps of the migration, the step at the index `i` migrates the version `i + 1`.
+/// A step migrates up to the given number of items and returns true when the whole state is migrated.
+pub(crate) const MIGRATIONS: &[fn(u32) -> bool] = &[migrate_v1_to_v2];
+
+/// Version of the current state layout
+pub const STATE_VERSION: u32 = MIGRATIONS.len() as u32 + 1;
+
+/// Storage key of the state version
+const STATE_VERSION_KEY: &[u8] = b"STATE_VERSION";
+
+//get the version of the stored state
+pub(crate) fn read_state_version() -> u32 {
+    env::storage_read(STATE_VERSION_KEY)
+        .map(|bytes| u32::try_from_slice(&bytes).expect("Invalid state version"))
+        .unwrap_or(1)
+}
+
+//save the version of the stored state
+pub(crate) fn write_state_version(version: u32) {
+    env::storage_write(STATE_VERSION_KEY, &version.try_to_vec().unwrap());
+}
+
+//run the step of the stored version on up to `limit` items, returns the version of the stored state after it
+pub(crate) fn migrate_page(limit: u32) -> u32 {
+    let version = read_state_version();
+    assert!(version <= STATE_VERSION, "State version {} is newer than the code", version);
+    
+    if version < STATE_VERSION && MIGRATIONS[version as usize - 1](limit) {
+        write_state_version(version + 1);
+        return version + 1;
+    }
+    version
+}
+
+//collections keep only their prefixes and lengths in the state, so the map is read with the new type
+//of the values and the values in the range of its indexes are written over with their converted values.
+//`insert` can't be used, it would read the old value with the new type.
+fn migrate_values<V1: BorshDeserialize + BorshSerialize, V2: BorshSerialize>(
+    map: &UnorderedMap<TournamentId, V1>,
+    indexes: Range<u64>,
+    mut convert: impl FnMut(&TournamentId, V1) -> V2,
+) {
+    let mut new_map: UnorderedMap<TournamentId, V2> = UnorderedMap::try_from_slice(&map.try_to_vec().unwrap()).unwrap();
+    for index in indexes {
+        let tournament_id = map.keys_as_vector().get(index).unwrap();
+        let value = map.values_as_vector().get(index).unwrap();
+        new_map.insert_raw(&tournament_id.try_to_vec().unwrap(), &convert(&tournament_id, value).try_to_vec().unwrap());
+    }
+}
+
+//same prefix as the contract hashes the tournament ID with the shift into
+fn hash_tournament_id(tournament_id: &str, shift: &str) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256((tournament_id.to_owned() + shift).as_bytes()));
+    hash
+}
+
+//same prefix as the contract hashes the account ID into
+fn hash_account_id(account_id: &AccountId) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&env::sha256(account_id.as_str().as_bytes()));
+    hash
+}
+
+/// The contract state of the version 1
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV1 {
+    pub owner_id: AccountId,
+    pub tournament: TournamentContractV1,
+    pub metadata: LazyOption<TournamentContractMetadata>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TournamentContractV1 {
+    pub players_per_tournament: LookupMap<TournamentId, UnorderedSet<AccountId>>,
+    pub winners_percents_per_tournament: LookupMap<TournamentId, LookupMap<u8,u8>>,
+    pub tournaments_by_id: LookupMap<TournamentId, TournamentV1>,
+    pub tournament_metadata_by_id: UnorderedMap<TournamentId, TournamentMetadataV1>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TournamentV1 {
+    pub owner_id: AccountId,
+    pub active: bool,
+    pub balance: u128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TournamentMetadataV1 {
+    pub name: String,
+    pub icon: Option<String>,
+    pub players_number: u8,
+    pub in_price: u128,
+}
+
+/// The contract state of the version 2
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractV2 {
+    pub owner_id: AccountId,
+    pub pending_owner_id: Option<AccountId>,
+    pub tournament: TournamentContractV2,
+    pub metadata: LazyOption<TournamentContractMetadata>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TournamentContractV2 {
+    pub players_per_tournament: LookupMap<TournamentId, UnorderedSet<AccountId>>,
+    pub winners_basis_points_per_tournament: LookupMap<TournamentId, UnorderedMap<u32,u16>>,
+    pub tournaments_by_id: LookupMap<TournamentId, TournamentV2>,
+    pub tournament_metadata_by_id: UnorderedMap<TournamentId, TournamentMetadataV2>,
+    pub tournaments_per_owner: LookupMap<AccountId, UnorderedSet<TournamentId>>,
+    pub tournaments_per_state: LookupMap<TournamentState, UnorderedSet<TournamentId>>,
+    pub stats: TournamentStatsV2,
+    pub paused: bool,
+    pub paused_tournaments: LookupSet<TournamentId>,
+    pub teams_per_tournament: LookupMap<TournamentId, LookupMap<AccountId, TeamV2>>,
+    pub team_members_per_tournament: LookupMap<TournamentId, LookupMap<AccountId, AccountId>>,
+    pub payers_per_tournament: LookupMap<TournamentId, LookupMap<AccountId, AccountId>>,
+    pub allowed_per_tournament: LookupMap<TournamentId, LookupSet<AccountId>>,
+    pub invite_codes_per_tournament: LookupMap<TournamentId, LookupMap<CryptoHash, bool>>,
+    pub sponsors_per_tournament: LookupMap<TournamentId, UnorderedMap<AccountId, Balance>>,
+    pub guarantees_per_tournament: LookupMap<TournamentId, Balance>,
+    pub payout_tiers_per_tournament: LookupMap<TournamentId, Vec<PayoutTierV2>>,
+    pub locked_payout_tiers_per_tournament: LookupMap<TournamentId, Option<u8>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TournamentV2 {
+    pub owner_id: AccountId,
+    pub pending_owner_id: Option<AccountId>,
+    pub state: TournamentState,
+    pub balance: u128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TournamentMetadataV2 {
+    pub name: String,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+    pub game: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<Base64VecU8>,
+    pub links: Option<Vec<String>>,
+    pub extra: Option<String>,
+    pub starts_at: Option<U64>,
+    pub ends_at: Option<U64>,
+    pub players_number: u32,
+    pub in_price: u128,
+    pub team_size: Option<u8>,
+    pub access: TournamentAccessV2,
+    pub gates: Option<Vec<EntryGateV2>>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum TournamentAccessV2 {
+    Public,
+    Allowlist,
+    InviteCode,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum EntryGateV2 {
+    Nft { contract_id: AccountId },
+    Ft { contract_id: AccountId, min_balance: U128 },
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct TeamV2 {
+    pub name: String,
+    pub members: Vec<AccountId>,
+    pub shares: Vec<u16>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PayoutTierV2 {
+    pub min_entrants: u32,
+    pub max_entrants: u32,
+    pub basis_points_map: HashMap<u32,u16>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct TournamentStatsV2 {
+    pub tournaments_total: u64,
+    pub tournaments_open: u64,
+    pub tournaments_completed: u64,
+    pub tournaments_cancelled: u64,
+    pub players_total: u64,
+    pub locked_balance: u128,
+    pub paid_out: u128,
+}
+
+/// Storage key of the progress of the step migrating the state in pages
+const MIGRATION_PROGRESS_KEY: &[u8] = b"MIGRATION_PROGRESS";
+
+/// Progress of the migration of the version 1 kept between its pages
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct MigrationProgressV1 {
+    //number of the tournaments migrated in the order of the metadata map
+    pub migrated: u64,
+    pub stats: TournamentStatsV2,
+}
+
+//add the tournament to the set of its owner like the contract does
+fn add_tournament_to_owner(
+    tournaments_per_owner: &mut LookupMap<AccountId, UnorderedSet<TournamentId>>,
+    owner_id: &AccountId,
+    tournament_id: &TournamentId,
+) {
+    let mut tournaments_set = tournaments_per_owner.get(owner_id).unwrap_or_else(|| {
+        UnorderedSet::new(
+            StorageKey::TournamentsPerOwnerInner { account_id_hash: hash_account_id(owner_id) }
+            .try_to_vec()
+            .unwrap(),
+        )
+    });
+    tournaments_set.insert(tournament_id);
+    tournaments_per_owner.insert(owner_id, &tournaments_set);
+}
+
+//add the tournament to the set of its state like the contract does
+fn add_tournament_to_state(
+    tournaments_per_state: &mut LookupMap<TournamentState, UnorderedSet<TournamentId>>,
+    state: TournamentState,
+    tournament_id: &TournamentId,
+) {
+    let mut tournaments_set = tournaments_per_state.get(&state).unwrap_or_else(|| {
+        UnorderedSet::new(
+            StorageKey::TournamentsPerStateInner { state }
+            .try_to_vec()
+            .unwrap(),
+        )
+    });
+    tournaments_set.insert(tournament_id);
+    tournaments_per_state.insert(&state, &tournaments_set);
+}
+
+//version 2: ownership transfer, rich metadata, tournament states with the owner and state indexes, the stats,
+//pausing, teams, sponsored entries, access modes, sponsors, guarantees, payout tiers and entry gates.
+//The tournaments of the version 1 have none of them. The prizes become whole basis points (60% is 6000)
+//kept only for the places with prizes, the NEAR paid out before the migration isn't known,
+//so the stats start counting it from zero.
+fn migrate_v1_to_v2(limit: u32) -> bool {
+    let old: ContractV1 = env::state_read().expect("No state to migrate");
+    let mut progress: MigrationProgressV1 = env::storage_read(MIGRATION_PROGRESS_KEY)
+        .map(|bytes| MigrationProgressV1::try_from_slice(&bytes).unwrap())
+        .unwrap_or_default();
+    let players_per_tournament = old.tournament.players_per_tournament;
+    let mut old_tournaments_by_id = old.tournament.tournaments_by_id;
+    let mut old_winners = old.tournament.winners_percents_per_tournament;
+
+    //the maps keep their prefixes, every old value is removed before the new one is inserted
+    let mut tournaments_by_id = LookupMap::new(StorageKey::TournamentsById);
+    let mut winners_basis_points_per_tournament = LookupMap::new(StorageKey::WinnersPercentPerTournament);
+    let mut tournaments_per_owner = LookupMap::new(StorageKey::TournamentsPerOwner);
+    let mut tournaments_per_state = LookupMap::new(StorageKey::TournamentsPerState);
+    let stats = &mut progress.stats;
+
+    let tournaments_number = old.tournament.tournament_metadata_by_id.len();
+    let page_end = tournaments_number.min(progress.migrated + u64::from(limit));
+    migrate_values(&old.tournament.tournament_metadata_by_id, progress.migrated..page_end, |tournament_id, old_metadata: TournamentMetadataV1| {
+        let old_tournament = old_tournaments_by_id.remove(tournament_id).unwrap();
+        let state = if old_tournament.active {
+            TournamentState::Open
+        } else {
+            TournamentState::Completed
+        };
+
+        if let Some(mut old_prizes) = old_winners.remove(tournament_id) {
+            //the old places can't be out of the players number
+            let mut prizes_map = UnorderedMap::new(
+                StorageKey::PrizesPerTournamentInner { tournament_id_hash: hash_tournament_id(tournament_id, "b") }
+                .try_to_vec()
+                .unwrap(),
+            );
+            for place in 1..=old_metadata.players_number {
+                if let Some(percents) = old_prizes.remove(&place) {
+                    prizes_map.insert(&u32::from(place), &(u16::from(percents) * 100));
+                }
+            }
+            winners_basis_points_per_tournament.insert(tournament_id, &prizes_map);
+        }
+
+        add_tournament_to_owner(&mut tournaments_per_owner, &old_tournament.owner_id, tournament_id);
+        add_tournament_to_state(&mut tournaments_per_state, state, tournament_id);
+
+        stats.tournaments_total += 1;
+        if old_tournament.active {
+            stats.tournaments_open += 1;
+        } else {
+            stats.tournaments_completed += 1;
+        }
+        stats.players_total += players_per_tournament.get(tournament_id).map_or(0, |players| players.len());
+        stats.locked_balance += old_tournament.balance;
+
+        tournaments_by_id.insert(tournament_id, &TournamentV2 {
+            owner_id: old_tournament.owner_id,
+            pending_owner_id: None,
+            state,
+            balance: old_tournament.balance,
+        });
+
+        TournamentMetadataV2 {
+            name: old_metadata.name,
+            icon: old_metadata.icon,
+            description: None,
+            game: None,
+            reference: None,
+            reference_hash: None,
+            links: None,
+            extra: None,
+            starts_at: None,
+            ends_at: None,
+            players_number: u32::from(old_metadata.players_number),
+            in_price: old_metadata.in_price,
+            team_size: None,
+            access: TournamentAccessV2::Public,
+            gates: None,
+        }
+    });
+
+    //the rest of the tournaments is migrated by the next pages
+    progress.migrated = page_end;
+    if page_end < tournaments_number {
+        env::storage_write(MIGRATION_PROGRESS_KEY, &progress.try_to_vec().unwrap());
+        return false;
+    }
+    env::storage_remove(MIGRATION_PROGRESS_KEY);
+
+    //the metadata map keeps its prefix and length, its values are of the new type now
+    let tournament_metadata_by_id = UnorderedMap::<TournamentId, TournamentMetadataV2>::try_from_slice(
+        &old.tournament.tournament_metadata_by_id.try_to_vec().unwrap()
+    ).unwrap();
+
+    env::state_write(&ContractV2 {
+        owner_id: old.owner_id,
+        pending_owner_id: None,
+        tournament: TournamentContractV2 {
+            players_per_tournament,
+            winners_basis_points_per_tournament,
+            tournaments_by_id,
+            tournament_metadata_by_id,
+            tournaments_per_owner,
+            tournaments_per_state,
+            stats: progress.stats,
+            paused: false,
+            paused_tournaments: LookupSet::new(StorageKey::PausedTournaments),
+            teams_per_tournament: LookupMap::new(StorageKey::TeamsPerTournament),
+            team_members_per_tournament: LookupMap::new(StorageKey::TeamMembersPerTournament),
+            payers_per_tournament: LookupMap::new(StorageKey::PayersPerTournament),
+            allowed_per_tournament: LookupMap::new(StorageKey::AllowedPerTournament),
+            invite_codes_per_tournament: LookupMap::new(StorageKey::InviteCodesPerTournament),
+            sponsors_per_tournament: LookupMap::new(StorageKey::SponsorsPerTournament),
+            guarantees_per_tournament: LookupMap::new(StorageKey::GuaranteesPerTournament),
+            payout_tiers_per_tournament: LookupMap::new(StorageKey::PayoutTiersPerTournament),
+            locked_payout_tiers_per_tournament: LookupMap::new(StorageKey::LockedPayoutTiersPerTournament),
+        },
+        metadata: old.metadata,
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::{testing_env, Gas};
+
+    fn v1_context() -> VMContextBuilder {
+        let mut context = VMContextBuilder::new();
+        context.current_account_id(accounts(0)).predecessor_account_id(accounts(0));
+        context
+    }
+
+    fn v1_state() -> ContractV1 {
+        testing_env!(v1_context().build());
+
+        ContractV1 {
+            owner_id: accounts(0),
+            tournament: TournamentContractV1 {
+                players_per_tournament: LookupMap::new(StorageKey::PlayersPerTournament),
+                winners_percents_per_tournament: LookupMap::new(StorageKey::WinnersPercentPerTournament),
+                tournaments_by_id: LookupMap::new(StorageKey::TournamentsById),
+                tournament_metadata_by_id: UnorderedMap::new(StorageKey::TournamentMetadataById),
+            },
+            metadata: LazyOption::new(
+                StorageKey::TournamentContractMetadata.try_to_vec().unwrap(),
+                Some(&TournamentContractMetadata { name: "Tournaments".to_string(), icon: None }),
+            ),
+        }
+    }
+
+    fn v1_tournament(
+        contract: &mut ContractV1,
+        tournament_id: &str,
+        owner_id: AccountId,
+        active: bool,
+        players: &[AccountId],
+    ) {
+        let tournament_id = tournament_id.to_string();
+
+        let mut players_set = UnorderedSet::new(format!("s{}", tournament_id).into_bytes());
+        for player in players {
+            players_set.insert(player);
+        }
+        contract.tournament.players_per_tournament.insert(&tournament_id, &players_set);
+
+        let mut prizes_map = LookupMap::new(format!("m{}", tournament_id).into_bytes());
+        prizes_map.insert(&1, &60);
+        prizes_map.insert(&2, &30);
+        contract.tournament.winners_percents_per_tournament.insert(&tournament_id, &prizes_map);
+
+        contract.tournament.tournaments_by_id.insert(&tournament_id, &TournamentV1 {
+            owner_id,
+            active,
+            balance: if active { 100 * players.len() as u128 } else { 10 },
+        });
+        contract.tournament.tournament_metadata_by_id.insert(&tournament_id, &TournamentMetadataV1 {
+            name: tournament_id.to_uppercase(),
+            icon: None,
+            players_number: 8,
+            in_price: 100,
+        });
+    }
+
+    //the state of the version 1 with three tournaments
+    fn write_v1_tournaments() {
+        let mut v1 = v1_state();
+        v1_tournament(&mut v1, "tournament-1", accounts(1), true, &[accounts(2), accounts(3)]);
+        v1_tournament(&mut v1, "tournament-2", accounts(1), false, &[accounts(2)]);
+        v1_tournament(&mut v1, "tournament-3", accounts(4), true, &[]);
+        env::state_write(&v1);
+    }
+
+    fn assert_v1_tournaments_migrated(contract: &Contract) {
+        assert_eq!(read_state_version(), STATE_VERSION);
+
+        let tournament = contract.display_tournament("tournament-1".to_string()).unwrap();
+        assert_eq!(tournament.owner_id, accounts(1));
+        assert_eq!(tournament.state, TournamentState::Open);
+        assert_eq!(tournament.metadata.name, "TOURNAMENT-1");
+        assert_eq!(tournament.metadata.description, None);
+        assert_eq!(tournament.first_place_prize, U64(6_000));
+        assert_eq!(tournament.prize_fond, U128(200));
+        assert_eq!(contract.display_freeplaces_in_tournament("tournament-1".to_string()), Some(U64(6)));
+
+        let tournament = contract.display_tournament("tournament-2".to_string()).unwrap();
+        assert_eq!(tournament.state, TournamentState::Completed);
+
+        assert_eq!(contract.tournament_supply_for_owner(accounts(1)), U128(2));
+        assert_eq!(contract.tournament_supply_for_state(TournamentState::Open), U128(2));
+        assert_eq!(contract.display_tournaments(None, None, None, None).len(), 3);
+
+        let stats = contract.tournament_stats();
+        assert_eq!(stats.tournaments_total, U64(3));
+        assert_eq!(stats.tournaments_completed, U64(1));
+        assert_eq!(stats.players_total, U64(3));
+        assert_eq!(stats.locked_balance, U128(210));
+
+        assert_eq!(contract.contract_owner(), (accounts(0), None));
+        assert_eq!(contract.contract_metadata().name, "Tournaments");
+    }
+
+    #[test]
+    fn migrate_v1_state() {
+        write_v1_tournaments();
+        assert_eq!(read_state_version(), 1);
+
+        let contract = Contract::migrate();
+        assert_v1_tournaments_migrated(&contract);
+    }
+
+    #[test]
+    fn migrate_v1_state_in_pages() {
+        write_v1_tournaments();
+
+        assert!(!Contract::migrate_page(2));
+        assert_eq!(read_state_version(), 1);
+        assert!(Contract::migrate_page(2));
+        assert!(Contract::migrate_page(2));
+
+        let contract = Contract::migrate();
+        assert_v1_tournaments_migrated(&contract);
+    }
+
+    //the biggest page of the tournaments of the version 1 in the module doc
+    const MAX_V1_TOURNAMENTS_PER_PAGE: u32 = 2;
+
+    #[test]
+    fn migrate_biggest_v1_state() {
+        let mut v1 = v1_state();
+        //every place of the biggest tournaments has a prize
+        for index in 0..MAX_V1_TOURNAMENTS_PER_PAGE {
+            let tournament_id = format!("tournament-{}", index);
+            let mut prizes_map = LookupMap::new(format!("m{}", tournament_id).into_bytes());
+            for place in 1..=u8::MAX {
+                prizes_map.insert(&place, &1);
+            }
+            v1.tournament.winners_percents_per_tournament.insert(&tournament_id, &prizes_map);
+            v1.tournament.tournaments_by_id.insert(&tournament_id, &TournamentV1 {
+                owner_id: accounts(1),
+                active: true,
+                balance: 0,
+            });
+            v1.tournament.tournament_metadata_by_id.insert(&tournament_id, &TournamentMetadataV1 {
+                name: tournament_id.to_uppercase(),
+                icon: None,
+                players_number: u8::MAX,
+                in_price: 100,
+            });
+        }
+        env::state_write(&v1);
+
+        //the migration is a new call with the 300 TGas of the upgrade keeping the storage
+        testing_env!(v1_context().prepaid_gas(Gas(300_000_000_000_000)).build());
+        let contract = Contract::migrate();
+        //the storage of the page takes up to 200 TGas, the rest is left for the execution of the code
+        assert!(env::used_gas().0 <= 200_000_000_000_000, "{:?}", env::used_gas());
+        assert_eq!(contract.tournament_stats().tournaments_total, U64(u64::from(MAX_V1_TOURNAMENTS_PER_PAGE)));
+    }
+
+    #[test]
+    fn migrate_current_state_is_noop() {
+        let mut context = VMContextBuilder::new();
+        context.current_account_id(accounts(0)).predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+
+        let contract = Contract::new_default_meta(accounts(0));
+        env::state_write(&contract);
+
+        let contract = Contract::migrate();
+        assert_eq!(read_state_version(), STATE_VERSION);
+        assert_eq!(contract.tournaments_total_supply(), U128(0));
+    }
+}
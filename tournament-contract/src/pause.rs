@@ -0,0 +1,50 @@
+//! Emergency pause of the contract or of a single tournament by the contract owner.
+//!
+//! While paused, players can't join and the prizes and fees can't be paid out.
+//! Leaving, cancelling (so the refunds) and the view calls keep working.
+
+use crate::*;
+use crate::tournament::events::{ContractPauseLog, ContractUnpauseLog};
+
+#[near_bindgen]
+impl Contract {
+    //pause the whole contract or the given tournament
+    pub fn pause(&mut self, tournament_id: Option<TournamentId>) {
+        self.assert_owner();
+
+        if let Some(tournament_id) = &tournament_id {
+            assert!(self.tournament.tournaments_by_id.get(tournament_id).is_some(), "No tournament");
+            assert!(self.tournament.paused_tournaments.insert(tournament_id), "Tournament is already paused");
+        } else {
+            assert!(!self.tournament.paused, "Contract is already paused");
+            self.tournament.paused = true;
+        }
+
+        ContractPauseLog{
+            tournament_id: tournament_id.as_ref(),
+        }.emit();
+    }
+
+    //unpause the whole contract or the given tournament
+    pub fn unpause(&mut self, tournament_id: Option<TournamentId>) {
+        self.assert_owner();
+
+        if let Some(tournament_id) = &tournament_id {
+            assert!(self.tournament.paused_tournaments.remove(tournament_id), "Tournament isn't paused");
+        } else {
+            assert!(self.tournament.paused, "Contract isn't paused");
+            self.tournament.paused = false;
+        }
+
+        ContractUnpauseLog{
+            tournament_id: tournament_id.as_ref(),
+        }.emit();
+    }
+
+    //check whether the whole contract or the given tournament is paused
+    pub fn is_paused(&self, tournament_id: Option<TournamentId>) -> bool {
+        self.tournament.paused || tournament_id
+            .map(|tournament_id| self.tournament.paused_tournaments.contains(&tournament_id))
+            .unwrap_or(false)
+    }
+}
@@ -0,0 +1,186 @@
+//! Prize payout arithmetic shared by the contract and the off-chain tools.
+//!
+//! Only `core` and `alloc` are used and there is no storage or `env` access, so the
+//! organisers' tools get exactly the amounts `reward_prizes` transfers.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The prize of one winner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrizeTransfer<A> {
+    pub place: u32,
+    pub account_id: A,
+    pub amount: u128,
+}
+
+/// Transfers of the payout ordered by place and what is left of the pot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutPlan<A> {
+    pub transfers: Vec<PrizeTransfer<A>>,
+    //sum of the transfers
+    pub paid_out: u128,
+    //the fee kept from the pot before the prizes
+    pub fee: u128,
+    //the rest of the pot after the fee and the prizes
+    pub remainder: u128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayoutError {
+    FeeAbovePot { fee: u128, pot: u128 },
+    NoPrize(u32),
+    PrizesAbovePot { paid_out: u128, pot: u128 },
+}
+
+impl fmt::Display for PayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayoutError::FeeAbovePot { fee, pot } => write!(f, "Fee {} is more than the prize fond {}", fee, pot),
+            PayoutError::NoPrize(place) => write!(f, "No prize for the place {}", place),
+            PayoutError::PrizesAbovePot { paid_out, pot } => write!(f, "Prizes {} are more than the prize fond {}", paid_out, pot),
+        }
+    }
+}
+
+/// The whole pot in basis points
+pub const BASIS_POINTS_TOTAL: u128 = 10_000;
+
+//calculates the basis points of the amount rounding down, so the prizes never
+//add up to more than the amount, the rounding dust stays in the remainder
+pub fn basis_points_of(basis_points: u128, amount: u128) -> u128 {
+    (basis_points * amount) / BASIS_POINTS_TOTAL
+}
+
+/// Split `pot` between the winners: the fee is kept first, then each winner gets
+/// the basis points of its place (`basis_points` of the place, `None` when the place has
+/// no prize) of the rest.
+pub fn compute_payout<A, F, W>(pot: u128, fee: u128, basis_points: F, winners: W) -> Result<PayoutPlan<A>, PayoutError>
+    where
+        F: Fn(u32) -> Option<u16>,
+        W: IntoIterator<Item = (u32, A)>,
+{
+    let prize_fond = pot.checked_sub(fee).ok_or(PayoutError::FeeAbovePot { fee, pot })?;
+
+    let mut transfers = Vec::new();
+    let mut paid_out: u128 = 0;
+    for (place, account_id) in winners {
+        let place_points = basis_points(place).ok_or(PayoutError::NoPrize(place))?;
+        let amount = basis_points_of(place_points as u128, prize_fond);
+        paid_out += amount;
+        transfers.push(PrizeTransfer { place, account_id, amount });
+    }
+    transfers.sort_by_key(|transfer| transfer.place);
+
+    let remainder = prize_fond.checked_sub(paid_out).ok_or(PayoutError::PrizesAbovePot { paid_out, pot: prize_fond })?;
+
+    Ok(PayoutPlan { transfers, paid_out, fee, remainder })
+}
+
+/// Split the prize of a team between its members by their shares, the rounding dust
+/// goes to the first member with a share.
+pub fn split_prize(amount: u128, shares: &[u16]) -> Vec<u128> {
+    let total: u128 = shares.iter().map(|share| *share as u128).sum();
+    if total == 0 {
+        return shares.iter().map(|_| 0).collect();
+    }
+
+    //split the quotient and the remainder separately to not overflow
+    let mut amounts: Vec<u128> = shares.iter()
+        .map(|share| (amount / total) * *share as u128 + (amount % total) * *share as u128 / total)
+        .collect();
+    let dust = amount - amounts.iter().sum::<u128>();
+    if let Some((first, _)) = amounts.iter_mut().zip(shares).find(|(_, share)| **share > 0) {
+        *first += dust;
+    }
+    amounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    fn basis_points(place: u32) -> Option<u16> {
+        match place {
+            1 => Some(5_000),
+            2 => Some(3_000),
+            3 => Some(1_000),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn payout_by_places() {
+        let plan = compute_payout(1_000, 0, basis_points, vec![(2, "bob"), (1, "alice")]).unwrap();
+
+        assert_eq!(plan.transfers, vec![
+            PrizeTransfer { place: 1, account_id: "alice", amount: 500 },
+            PrizeTransfer { place: 2, account_id: "bob", amount: 300 },
+        ]);
+        assert_eq!(plan.paid_out, 800);
+        assert_eq!(plan.remainder, 200);
+    }
+
+    #[test]
+    fn payout_after_fee() {
+        let plan = compute_payout(1_100, 100, basis_points, vec![(1, "alice"), (2, "bob"), (3, "carol")]).unwrap();
+
+        assert_eq!(plan.paid_out, 900);
+        assert_eq!(plan.fee, 100);
+        assert_eq!(plan.remainder, 100);
+    }
+
+    #[test]
+    fn payout_rounds_down() {
+        //30 percents of 7 are 2.1
+        let plan = compute_payout(7, 0, basis_points, vec![(2, "bob")]).unwrap();
+        assert_eq!(plan.transfers[0].amount, 2);
+        assert_eq!(plan.remainder, 5);
+    }
+
+    #[test]
+    fn whole_pot_payout_of_odd_pot() {
+        //50/50 of 201 can't pay 100.5 each, the dust stays in the remainder
+        let plan = compute_payout(201, 0, |_| Some(5_000), vec![(1, "alice"), (2, "bob")]).unwrap();
+        assert_eq!(plan.paid_out, 200);
+        assert_eq!(plan.remainder, 1);
+
+        let plan = compute_payout(7, 0, |place| Some(if place == 1 { 6_667 } else { 3_333 }), vec![(1, "alice"), (2, "bob")]).unwrap();
+        assert_eq!(plan.paid_out, 6);
+        assert_eq!(plan.remainder, 1);
+    }
+
+    #[test]
+    fn payout_by_basis_points() {
+        //12.5 percents of 1_000 and 0.01 percent of 10_000
+        assert_eq!(basis_points_of(1_250, 1_000), 125);
+        assert_eq!(basis_points_of(1, 10_000), 1);
+        let plan = compute_payout(1_000_000, 0, |place| if place <= 1_000 { Some(10) } else { None }, vec![(1_000, "alice"), (300, "bob")]).unwrap();
+        assert_eq!(plan.paid_out, 2_000);
+        assert_eq!(compute_payout(1_000, 0, |_| None, vec![(1_001, "carol")]), Err(PayoutError::NoPrize(1_001)));
+    }
+
+    #[test]
+    fn payout_errors() {
+        assert_eq!(compute_payout(1_000, 0, basis_points, vec![(4, "dave")]), Err(PayoutError::NoPrize(4)));
+        assert_eq!(
+            compute_payout::<&str, _, _>(100, 200, basis_points, vec![]),
+            Err(PayoutError::FeeAbovePot { fee: 200, pot: 100 }),
+        );
+        assert_eq!(
+            compute_payout(101, 0, |_| Some(6_000), vec![(1, "alice"), (2, "bob")]),
+            Err(PayoutError::PrizesAbovePot { paid_out: 120, pot: 101 }),
+        );
+    }
+
+    #[test]
+    fn split_prize_by_shares() {
+        assert_eq!(split_prize(100, &[1, 1]), vec![50, 50]);
+        assert_eq!(split_prize(100, &[3, 1]), vec![75, 25]);
+        //the dust goes to the first member
+        assert_eq!(split_prize(100, &[1, 1, 1]), vec![34, 33, 33]);
+        assert_eq!(split_prize(11, &[0, 1, 1]), vec![0, 6, 5]);
+        assert_eq!(split_prize(u128::MAX, &[u16::MAX, 1]).iter().sum::<u128>(), u128::MAX);
+    }
+}
@@ -0,0 +1,340 @@
+use crate::*;
+use near_sdk::collections::LookupSet;
+use crate::tournament::events::{TournamentAllowlistUpdateLog, TournamentInviteCodesUpdateLog};
+use crate::tournament::internal::{hash_invite_code, hash_tournament_id};
+
+pub trait TournamentContractAccess {
+    //let the accounts enter the allowlist tournament
+    fn allowlist_add(&mut self, tournament_id: TournamentId, account_ids: Vec<AccountId>);
+
+    //take the accounts off the allowlist, the players who already joined keep their seats
+    fn allowlist_remove(&mut self, tournament_id: TournamentId, account_ids: Vec<AccountId>);
+
+    //check the account is on the allowlist of the tournament
+    fn is_allowed(&self, tournament_id: TournamentId, account_id: AccountId) -> bool;
+
+    //add the sha256 hashes of the invite codes, single use codes are spent by the first entrance
+    fn add_invite_codes(&mut self, tournament_id: TournamentId, code_hashes: Vec<Base64VecU8>, single_use: Option<bool>);
+
+    //remove the invite codes by their hashes
+    fn remove_invite_codes(&mut self, tournament_id: TournamentId, code_hashes: Vec<Base64VecU8>);
+
+    //check the invite code can be used to enter the tournament
+    fn is_invite_code_valid(&self, tournament_id: TournamentId, invite_code: String) -> bool;
+}
+
+//turns the hash passed in by the owner into the stored one
+fn to_code_hash(code_hash: &Base64VecU8) -> CryptoHash {
+    assert_eq!(code_hash.0.len(), INVITE_CODE_HASH_LENGTH, "Invite code hash should be {} bytes", INVITE_CODE_HASH_LENGTH);
+
+    let mut hash = CryptoHash::default();
+    hash.copy_from_slice(&code_hash.0);
+    hash
+}
+
+impl TournamentContract {
+    //check the tournament exists and the owner calls the method
+    fn internal_assert_tournament_owner(&self, tournament_id: &TournamentId) {
+        let tournament = self.tournaments_by_id.get(tournament_id).expect("No tournament");
+
+        assert_eq!(env::predecessor_account_id(), tournament.owner_id, "Owner's method");
+    }
+}
+
+impl TournamentContractAccess for TournamentContract {
+    //let the accounts enter the allowlist tournament
+    fn allowlist_add(&mut self, tournament_id: TournamentId, account_ids: Vec<AccountId>) {
+        self.internal_assert_tournament_owner(&tournament_id);
+
+        let mut allowed_set = self.allowed_per_tournament.get(&tournament_id).unwrap_or_else(|| {
+            //if the tournament doesn't have an allowlist, we create a new set
+            LookupSet::new(
+                StorageKey::AllowedPerTournamentInner {
+                    //we get a new unique prefix for the collection
+                    tournament_id_hash: hash_tournament_id(&tournament_id, "a"),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+
+        for account_id in account_ids.iter() {
+            allowed_set.insert(account_id);
+        }
+
+        self.allowed_per_tournament.insert(&tournament_id, &allowed_set);
+
+        TournamentAllowlistUpdateLog{
+            tournament_id: &tournament_id,
+            added: &account_ids,
+            removed: &[],
+        }.emit();
+    }
+
+    //take the accounts off the allowlist, the players who already joined keep their seats
+    fn allowlist_remove(&mut self, tournament_id: TournamentId, account_ids: Vec<AccountId>) {
+        self.internal_assert_tournament_owner(&tournament_id);
+
+        if let Some(mut allowed_set) = self.allowed_per_tournament.get(&tournament_id) {
+            for account_id in account_ids.iter() {
+                allowed_set.remove(account_id);
+            }
+        }
+
+        TournamentAllowlistUpdateLog{
+            tournament_id: &tournament_id,
+            added: &[],
+            removed: &account_ids,
+        }.emit();
+    }
+
+    //check the account is on the allowlist of the tournament
+    fn is_allowed(&self, tournament_id: TournamentId, account_id: AccountId) -> bool {
+        self.internal_is_allowed(&tournament_id, &account_id)
+    }
+
+    //add the sha256 hashes of the invite codes, single use codes are spent by the first entrance
+    fn add_invite_codes(&mut self, tournament_id: TournamentId, code_hashes: Vec<Base64VecU8>, single_use: Option<bool>) {
+        self.internal_assert_tournament_owner(&tournament_id);
+
+        let single_use = single_use.unwrap_or(false);
+
+        let mut codes_map = self.invite_codes_per_tournament.get(&tournament_id).unwrap_or_else(|| {
+            //if the tournament doesn't have any invite codes, we create a new map
+            LookupMap::new(
+                StorageKey::InviteCodesPerTournamentInner {
+                    //we get a new unique prefix for the collection
+                    tournament_id_hash: hash_tournament_id(&tournament_id, "i"),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+
+        for code_hash in code_hashes.iter() {
+            codes_map.insert(&to_code_hash(code_hash), &single_use);
+        }
+
+        self.invite_codes_per_tournament.insert(&tournament_id, &codes_map);
+
+        TournamentInviteCodesUpdateLog{
+            tournament_id: &tournament_id,
+            added: &code_hashes,
+            removed: &[],
+            single_use: Some(single_use),
+        }.emit();
+    }
+
+    //remove the invite codes by their hashes
+    fn remove_invite_codes(&mut self, tournament_id: TournamentId, code_hashes: Vec<Base64VecU8>) {
+        self.internal_assert_tournament_owner(&tournament_id);
+
+        if let Some(mut codes_map) = self.invite_codes_per_tournament.get(&tournament_id) {
+            for code_hash in code_hashes.iter() {
+                codes_map.remove(&to_code_hash(code_hash));
+            }
+        }
+
+        TournamentInviteCodesUpdateLog{
+            tournament_id: &tournament_id,
+            added: &[],
+            removed: &code_hashes,
+            single_use: None,
+        }.emit();
+    }
+
+    //check the invite code can be used to enter the tournament
+    fn is_invite_code_valid(&self, tournament_id: TournamentId, invite_code: String) -> bool {
+        matches!(
+            self.invite_codes_per_tournament.get(&tournament_id),
+            Some(codes_map) if codes_map.contains_key(&hash_invite_code(&invite_code))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_parser::*;
+    use crate::tournament::testing::*;
+    use near_sdk::test_utils::accounts;
+
+    const TOURNAMENT_ID: &str = "tournament-1";
+
+    //a tournament of accounts(0) for three players in the access mode
+    fn setup(access: TournamentAccess) -> TournamentContract {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 3, IN_PRICE);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            access: Some(access),
+            ..Default::default()
+        });
+        contract
+    }
+
+    fn code_hash(invite_code: &str) -> Base64VecU8 {
+        Base64VecU8(env::sha256(invite_code.as_bytes()))
+    }
+
+    //join the tournament with the exact in price and the invite code
+    fn join_with_code(contract: &mut TournamentContract, player_id: AccountId, invite_code: &str) {
+        set_context(player_id, IN_PRICE);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), Some(invite_code.to_string()));
+    }
+
+    #[test]
+    fn public_by_default() {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 3, IN_PRICE);
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.metadata.access, TournamentAccess::Public);
+
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+    }
+
+    #[test]
+    fn allowlist_lets_allowed_accounts_in() {
+        let mut contract = setup(TournamentAccess::Allowlist);
+
+        set_context(accounts(0), 0);
+        contract.allowlist_add(TOURNAMENT_ID.to_string(), vec![accounts(1), accounts(2)]);
+        assert!(contract.is_allowed(TOURNAMENT_ID.to_string(), accounts(1)));
+        assert_eq!(logged_events(), vec![
+            TournamentEventData::TournamentAllowlistUpdate(vec![TournamentAllowlistUpdateData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                added: vec![accounts(1), accounts(2)],
+                removed: vec![],
+            }]),
+        ]);
+
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(2)));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not on the allowlist")]
+    fn allowlist_blocks_other_accounts() {
+        let mut contract = setup(TournamentAccess::Allowlist);
+
+        set_context(accounts(0), 0);
+        contract.allowlist_add(TOURNAMENT_ID.to_string(), vec![accounts(1)]);
+
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not on the allowlist")]
+    fn allowlist_remove_blocks_account() {
+        let mut contract = setup(TournamentAccess::Allowlist);
+
+        set_context(accounts(0), 0);
+        contract.allowlist_add(TOURNAMENT_ID.to_string(), vec![accounts(1)]);
+        contract.allowlist_remove(TOURNAMENT_ID.to_string(), vec![accounts(1)]);
+        assert!(!contract.is_allowed(TOURNAMENT_ID.to_string(), accounts(1)));
+
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not on the allowlist")]
+    fn allowlist_checks_sponsored_player() {
+        let mut contract = setup(TournamentAccess::Allowlist);
+
+        set_context(accounts(0), 0);
+        contract.allowlist_add(TOURNAMENT_ID.to_string(), vec![accounts(1)]);
+
+        //the payer is allowed, the player isn't
+        set_context(accounts(1), IN_PRICE);
+        contract.participate_tournament_for(TOURNAMENT_ID.to_string(), accounts(2), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not on the allowlist")]
+    fn allowlist_checks_team_members() {
+        let mut contract = new_contract();
+        create_team_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 2, 2);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            access: Some(TournamentAccess::Allowlist),
+            ..Default::default()
+        });
+        contract.allowlist_add(TOURNAMENT_ID.to_string(), vec![accounts(1)]);
+
+        set_context(accounts(1), IN_PRICE);
+        contract.register_team(TOURNAMENT_ID.to_string(), "Team".to_string(), vec![accounts(1), accounts(2)], None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner's method")]
+    fn allowlist_add_by_other_account() {
+        let mut contract = setup(TournamentAccess::Allowlist);
+
+        set_context(accounts(1), 0);
+        contract.allowlist_add(TOURNAMENT_ID.to_string(), vec![accounts(1)]);
+    }
+
+    #[test]
+    fn invite_code_can_be_reused() {
+        let mut contract = setup(TournamentAccess::InviteCode);
+
+        set_context(accounts(0), 0);
+        contract.add_invite_codes(TOURNAMENT_ID.to_string(), vec![code_hash("secret")], None);
+        assert!(contract.is_invite_code_valid(TOURNAMENT_ID.to_string(), "secret".to_string()));
+        assert!(!contract.is_invite_code_valid(TOURNAMENT_ID.to_string(), "guess".to_string()));
+        assert_eq!(logged_events(), vec![
+            TournamentEventData::TournamentInviteCodesUpdate(vec![TournamentInviteCodesUpdateData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                added: vec![code_hash("secret")],
+                removed: vec![],
+                single_use: Some(false),
+            }]),
+        ]);
+
+        join_with_code(&mut contract, accounts(1), "secret");
+        join_with_code(&mut contract, accounts(2), "secret");
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid invite code")]
+    fn single_use_invite_code_is_spent() {
+        let mut contract = setup(TournamentAccess::InviteCode);
+
+        set_context(accounts(0), 0);
+        contract.add_invite_codes(TOURNAMENT_ID.to_string(), vec![code_hash("secret")], Some(true));
+
+        join_with_code(&mut contract, accounts(1), "secret");
+        assert!(!contract.is_invite_code_valid(TOURNAMENT_ID.to_string(), "secret".to_string()));
+
+        join_with_code(&mut contract, accounts(2), "secret");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid invite code")]
+    fn removed_invite_code_is_invalid() {
+        let mut contract = setup(TournamentAccess::InviteCode);
+
+        set_context(accounts(0), 0);
+        contract.add_invite_codes(TOURNAMENT_ID.to_string(), vec![code_hash("secret")], None);
+        contract.remove_invite_codes(TOURNAMENT_ID.to_string(), vec![code_hash("secret")]);
+
+        join_with_code(&mut contract, accounts(1), "secret");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invite code is required")]
+    fn participate_without_invite_code() {
+        let mut contract = setup(TournamentAccess::InviteCode);
+
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invite code hash should be 32 bytes")]
+    fn add_invite_code_in_plain_text() {
+        let mut contract = setup(TournamentAccess::InviteCode);
+
+        set_context(accounts(0), 0);
+        contract.add_invite_codes(TOURNAMENT_ID.to_string(), vec![Base64VecU8(b"secret".to_vec())], None);
+    }
+}
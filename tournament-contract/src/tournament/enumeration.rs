@@ -2,24 +2,347 @@ use crate::*;
 
 pub trait TournamentContractEnumeration{
     //Query for  tournaments on the contract regardless of the ID using pagination
-    fn display_tournaments(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTournament>;
+    fn display_tournaments(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        filter: Option<TournamentFilter>,
+        reverse: Option<bool>
+    ) -> Vec<JsonTournament>;
+
+    //get the total number of tournaments on the contract
+    fn tournaments_total_supply(&self) -> U128;
+
+    //get the aggregate counters of the contract
+    fn tournament_stats(&self) -> JsonTournamentStats;
+
+    //get the number of tournaments for an owner
+    fn tournament_supply_for_owner(&self, account_id: AccountId) -> U128;
+
+    //Query for all the tournaments of an owner using pagination
+    fn tournaments_for_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTournament>;
+
+    //get the number of tournaments in a state
+    fn tournament_supply_for_state(&self, state: TournamentState) -> U128;
+
+    //Query for all the tournaments in a state using pagination
+    fn tournaments_for_state(&self, state: TournamentState, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTournament>;
+
+    //Query for the players of a tournament using pagination
+    fn tournament_players(&self, tournament_id: TournamentId, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId>;
 }
 
-impl TournamentContractEnumeration for TournamentContract {    
-    //Query for  tournaments on the contract regardless of the ID using pagination
-    fn display_tournaments(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTournament> {
+impl TournamentContract {
+    //page through a set of tournament IDs
+    fn internal_display_tournaments_set(
+        &self,
+        tournaments_set: &UnorderedSet<TournamentId>,
+        from_index: Option<U128>,
+        limit: Option<u64>
+    ) -> Vec<JsonTournament> {
         //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
         let start = u128::from(from_index.unwrap_or(U128(0)));
 
-        //iterate through each tournament using an iterator
-        self.tournament_metadata_by_id.keys()
+        tournaments_set.iter()
             //skip to the index we specified in the start variable
-            .skip(start as usize) 
+            .skip(start as usize)
             //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
-            .take(limit.unwrap_or(50) as usize) 
-            //we'll map the token IDs which are strings into Json Tokens
-            .map(|tournament_id| self.display_tournament(tournament_id.clone()).unwrap())
-            //since we turned the keys into an iterator, we need to turn it back into a vector to return
+            .take(limit.unwrap_or(50) as usize)
+            //we'll map the tournament IDs into Json Tournaments
+            .map(|tournament_id| self.display_tournament(tournament_id).unwrap())
+            //turn it back into a vector to return
             .collect()
-    }    
+    }
+
+    //check the tournament matches all the conditions of the filter
+    fn internal_tournament_matches(&self, tournament_id: &TournamentId, filter: &TournamentFilter) -> bool {
+        let tournament = self.tournaments_by_id.get(tournament_id).unwrap();
+        let metadata = self.tournament_metadata_by_id.get(tournament_id).unwrap();
+
+        if let Some(state) = filter.state {
+            if tournament.state != state {
+                return false;
+            }
+        }
+        if let Some(owner_id) = &filter.owner_id {
+            if &tournament.owner_id != owner_id {
+                return false;
+            }
+        }
+        if let Some(min_in_price) = &filter.min_in_price {
+            if metadata.in_price < min_in_price.0 {
+                return false;
+            }
+        }
+        if let Some(max_in_price) = &filter.max_in_price {
+            if metadata.in_price > max_in_price.0 {
+                return false;
+            }
+        }
+        if let Some(has_free_places) = filter.has_free_places {
+            let free_places = metadata.players_number > self.internal_get_players_number_in_tournament(tournament_id);
+            if free_places != has_free_places {
+                return false;
+            }
+        }
+        if let Some(game) = &filter.game {
+            if metadata.game.as_ref() != Some(game) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl TournamentContractEnumeration for TournamentContract {
+    //Query for  tournaments on the contract regardless of the ID using pagination.
+    //`from_index` is the position of the tournament in the creation order, so it stays the same
+    //while new tournaments are added. `limit` is the number of positions scanned, so a filtered
+    //page can be shorter than `limit`: the next page starts at `from_index + limit`
+    //(or `from_index - limit` in reverse order).
+    fn display_tournaments(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+        filter: Option<TournamentFilter>,
+        reverse: Option<bool>
+    ) -> Vec<JsonTournament> {
+        //tournament IDs in the creation order
+        let tournament_ids = self.tournament_metadata_by_id.keys_as_vector();
+        let len = tournament_ids.len();
+
+        //if we didn't specify a limit, use 50
+        let limit = limit.unwrap_or(50);
+
+        //positions of the tournaments to scan
+        let positions: Vec<u64> = if reverse.unwrap_or(false) {
+            if len == 0 {
+                return vec![];
+            }
+            //where to start pagination - if we have a from_index, we'll use that - otherwise start from the newest one
+            let start = from_index.map(u128::from).unwrap_or((len - 1) as u128).min((len - 1) as u128) as u64;
+            (0..=start).rev().take(limit as usize).collect()
+        } else {
+            //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+            let start = u128::from(from_index.unwrap_or(U128(0))).min(len as u128) as u64;
+            (start..len).take(limit as usize).collect()
+        };
+
+        let filter = filter.unwrap_or_default();
+
+        positions.into_iter()
+            //get the tournament ID at each position
+            .filter_map(|index| tournament_ids.get(index))
+            //skip the tournaments not matching the filter
+            .filter(|tournament_id| self.internal_tournament_matches(tournament_id, &filter))
+            //we'll map the tournament IDs into Json Tournaments
+            .map(|tournament_id| self.display_tournament(tournament_id).unwrap())
+            //turn it back into a vector to return
+            .collect()
+    }
+
+    //get the total number of tournaments on the contract
+    fn tournaments_total_supply(&self) -> U128 {
+        U128(self.stats.tournaments_total as u128)
+    }
+
+    //get the aggregate counters of the contract
+    fn tournament_stats(&self) -> JsonTournamentStats {
+        JsonTournamentStats {
+            tournaments_total: self.stats.tournaments_total.into(),
+            tournaments_open: self.stats.tournaments_open.into(),
+            tournaments_completed: self.stats.tournaments_completed.into(),
+            tournaments_cancelled: self.stats.tournaments_cancelled.into(),
+            players_total: self.stats.players_total.into(),
+            locked_balance: self.stats.locked_balance.into(),
+            paid_out: self.stats.paid_out.into(),
+        }
+    }
+
+    //get the number of tournaments for an owner
+    fn tournament_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        //get the set of tournaments for the passed in owner
+        if let Some(tournaments_set) = self.tournaments_per_owner.get(&account_id) {
+            U128(tournaments_set.len() as u128)
+        } else {
+            //if there isn't a set of tournaments for the passed in owner ID, we'll return 0
+            U128(0)
+        }
+    }
+
+    //Query for all the tournaments of an owner using pagination
+    fn tournaments_for_owner(&self, account_id: AccountId, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTournament> {
+        if let Some(tournaments_set) = self.tournaments_per_owner.get(&account_id) {
+            self.internal_display_tournaments_set(&tournaments_set, from_index, limit)
+        } else {
+            vec![]
+        }
+    }
+
+    //get the number of tournaments in a state
+    fn tournament_supply_for_state(&self, state: TournamentState) -> U128 {
+        if let Some(tournaments_set) = self.tournaments_per_state.get(&state) {
+            U128(tournaments_set.len() as u128)
+        } else {
+            U128(0)
+        }
+    }
+
+    //Query for all the tournaments in a state using pagination
+    fn tournaments_for_state(&self, state: TournamentState, from_index: Option<U128>, limit: Option<u64>) -> Vec<JsonTournament> {
+        if let Some(tournaments_set) = self.tournaments_per_state.get(&state) {
+            self.internal_display_tournaments_set(&tournaments_set, from_index, limit)
+        } else {
+            vec![]
+        }
+    }
+
+    //Query for the players of a tournament using pagination
+    fn tournament_players(&self, tournament_id: TournamentId, from_index: Option<U128>, limit: Option<u64>) -> Vec<AccountId> {
+        if let Some(players_set) = self.players_per_tournament.get(&tournament_id) {
+            players_set.iter()
+                .skip(u128::from(from_index.unwrap_or(U128(0))) as usize)
+                .take(limit.unwrap_or(50) as usize)
+                .collect()
+        } else {
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tournament::testing::*;
+    use near_sdk::test_utils::accounts;
+
+    //five tournaments: "t-0".."t-4", the even ones of accounts(1) and the odd ones of accounts(2),
+    //"t-0" is full, "t-1" is completed and "t-4" has the in price of 500
+    fn setup() -> TournamentContract {
+        let mut contract = new_contract();
+        for index in 0..5 {
+            let owner_id = if index % 2 == 0 { accounts(1) } else { accounts(2) };
+            let in_price = if index == 4 { 500 } else { IN_PRICE };
+            create_tournament(&mut contract, &format!("t-{}", index), owner_id, 2, in_price);
+        }
+
+        join(&mut contract, "t-0", accounts(3));
+        join(&mut contract, "t-0", accounts(4));
+
+        set_context(accounts(2), 0);
+        contract.reward_prizes("t-1".to_string(), HashMap::new());
+
+        contract
+    }
+
+    fn ids(tournaments: Vec<JsonTournament>) -> Vec<String> {
+        tournaments.into_iter().map(|tournament| tournament.tournament_id).collect()
+    }
+
+    #[test]
+    fn display_tournaments_pages() {
+        let contract = setup();
+
+        assert_eq!(ids(contract.display_tournaments(None, None, None, None)), vec!["t-0", "t-1", "t-2", "t-3", "t-4"]);
+        assert_eq!(ids(contract.display_tournaments(Some(U128(1)), Some(2), None, None)), vec!["t-1", "t-2"]);
+        assert_eq!(ids(contract.display_tournaments(Some(U128(3)), Some(10), None, None)), vec!["t-3", "t-4"]);
+    }
+
+    #[test]
+    fn display_tournaments_bounds() {
+        let contract = setup();
+
+        assert!(contract.display_tournaments(Some(U128(5)), None, None, None).is_empty());
+        assert!(contract.display_tournaments(Some(U128(u128::MAX)), None, None, None).is_empty());
+        assert!(contract.display_tournaments(None, Some(0), None, None).is_empty());
+
+        let empty = new_contract();
+        assert!(empty.display_tournaments(None, None, None, None).is_empty());
+        assert!(empty.display_tournaments(None, None, None, Some(true)).is_empty());
+    }
+
+    #[test]
+    fn display_tournaments_reverse() {
+        let contract = setup();
+
+        assert_eq!(ids(contract.display_tournaments(None, Some(2), None, Some(true))), vec!["t-4", "t-3"]);
+        assert_eq!(ids(contract.display_tournaments(Some(U128(1)), None, None, Some(true))), vec!["t-1", "t-0"]);
+        //the start past the newest tournament is clamped to it
+        assert_eq!(ids(contract.display_tournaments(Some(U128(100)), Some(1), None, Some(true))), vec!["t-4"]);
+    }
+
+    #[test]
+    fn display_tournaments_filtered() {
+        let contract = setup();
+
+        let filter = |filter: TournamentFilter| ids(contract.display_tournaments(None, None, Some(filter), None));
+
+        assert_eq!(filter(TournamentFilter { state: Some(TournamentState::Completed), ..Default::default() }), vec!["t-1"]);
+        assert_eq!(filter(TournamentFilter { owner_id: Some(accounts(2)), ..Default::default() }), vec!["t-1", "t-3"]);
+        assert_eq!(filter(TournamentFilter { min_in_price: Some(U128(200)), ..Default::default() }), vec!["t-4"]);
+        assert_eq!(filter(TournamentFilter { max_in_price: Some(U128(200)), ..Default::default() }), vec!["t-0", "t-1", "t-2", "t-3"]);
+        assert_eq!(filter(TournamentFilter { has_free_places: Some(false), ..Default::default() }), vec!["t-0"]);
+        assert!(filter(TournamentFilter { game: Some("chess".to_string()), ..Default::default() }).is_empty());
+        assert_eq!(
+            filter(TournamentFilter { state: Some(TournamentState::Open), owner_id: Some(accounts(1)), has_free_places: Some(true), ..Default::default() }),
+            vec!["t-2", "t-4"],
+        );
+
+        //the limit counts the scanned positions, not the matches
+        let filter = TournamentFilter { owner_id: Some(accounts(2)), ..Default::default() };
+        assert_eq!(ids(contract.display_tournaments(Some(U128(2)), Some(1), Some(filter), None)), Vec::<String>::new());
+    }
+
+    #[test]
+    fn tournaments_total_supply_and_stats() {
+        let contract = setup();
+
+        assert_eq!(contract.tournaments_total_supply(), U128(5));
+
+        let stats = contract.tournament_stats();
+        assert_eq!(stats.tournaments_total, U64(5));
+        assert_eq!(stats.tournaments_open, U64(4));
+        assert_eq!(stats.tournaments_completed, U64(1));
+        assert_eq!(stats.tournaments_cancelled, U64(0));
+        assert_eq!(stats.players_total, U64(2));
+        assert_eq!(stats.locked_balance, U128(2 * IN_PRICE));
+        assert_eq!(stats.paid_out, U128(0));
+    }
+
+    #[test]
+    fn tournaments_for_owner_pages() {
+        let contract = setup();
+
+        assert_eq!(contract.tournament_supply_for_owner(accounts(1)), U128(3));
+        assert_eq!(contract.tournament_supply_for_owner(accounts(5)), U128(0));
+
+        assert_eq!(ids(contract.tournaments_for_owner(accounts(1), None, None)), vec!["t-0", "t-2", "t-4"]);
+        assert_eq!(ids(contract.tournaments_for_owner(accounts(1), Some(U128(1)), Some(1))), vec!["t-2"]);
+        assert!(contract.tournaments_for_owner(accounts(1), Some(U128(3)), None).is_empty());
+        assert!(contract.tournaments_for_owner(accounts(5), None, None).is_empty());
+    }
+
+    #[test]
+    fn tournament_players_pages() {
+        let contract = setup();
+
+        assert_eq!(contract.tournament_players("t-0".to_string(), None, None), vec![accounts(3), accounts(4)]);
+        assert_eq!(contract.tournament_players("t-0".to_string(), Some(U128(1)), Some(5)), vec![accounts(4)]);
+        assert!(contract.tournament_players("t-0".to_string(), Some(U128(2)), None).is_empty());
+        assert!(contract.tournament_players("t-2".to_string(), None, None).is_empty());
+        assert!(contract.tournament_players("t-9".to_string(), None, None).is_empty());
+    }
+
+    #[test]
+    fn tournaments_for_state_pages() {
+        let contract = setup();
+
+        assert_eq!(contract.tournament_supply_for_state(TournamentState::Open), U128(4));
+        assert_eq!(contract.tournament_supply_for_state(TournamentState::Cancelled), U128(0));
+
+        assert_eq!(ids(contract.tournaments_for_state(TournamentState::Completed, None, None)), vec!["t-1"]);
+        assert_eq!(contract.tournaments_for_state(TournamentState::Open, Some(U128(1)), Some(2)).len(), 2);
+        assert!(contract.tournaments_for_state(TournamentState::Open, Some(U128(4)), None).is_empty());
+        assert!(contract.tournaments_for_state(TournamentState::Cancelled, None, None).is_empty());
+    }
 }
@@ -1,6 +1,54 @@
-use crate::event::NearEvent;
+//! Events of the tournament contract logged in the `EVENT_JSON:<json>` format.
+//!
+//! Schema of the version 2.0.0 (every event is logged with a list of data objects):
+//!
+//! ```text
+//! {"standard": "tournament", "version": "2.0.0", "event": "<event>", "data": [<data>, ...]}
+//!
+//! tournament_create          {"tournament_id": string, "owner_id": string, "players_number": number, "in_price": string}
+//! tournament_entrance        {"partisipator_id": string, "tournament_id": string, "payer_id"?: string}
+//! tournament_prizes_reward   {"tournament_id": string, "rewarded_amount": string,
+//!                             "winners": [{"place": number, "account_id": string, "amount": string}]}
+//! tournament_update          {"tournament_id": string, "updated_fields": [string]}
+//! tournament_owner_propose   {"tournament_id": string, "old_owner_id": string, "new_owner_id": string}
+//! tournament_owner_accept    {"tournament_id": string, "old_owner_id": string, "new_owner_id": string}
+//! contract_owner_propose     {"old_owner_id": string, "new_owner_id": string}
+//! contract_owner_accept      {"old_owner_id": string, "new_owner_id": string}
+//! tournament_owner_cancel    {"tournament_id": string, "old_owner_id": string, "new_owner_id": string}
+//! contract_owner_cancel      {"old_owner_id": string, "new_owner_id": string}
+//! tournament_cancel          {"tournament_id": string, "refunded_amount": string}
+//! tournament_leave           {"tournament_id": string, "player_id": string}
+//! tournament_refund          {"tournament_id": string, "account_id": string, "amount": string}
+//! tournament_fees_withdraw   {"tournament_id": string, "owner_id": string, "amount": string}
+//! contract_upgrade_stage     {"code_hash": string, "apply_after": string}
+//! contract_upgrade_cancel    {"code_hash": string}
+//! contract_upgrade           {"code_hash": string}
+//! contract_pause             {"tournament_id": string | null}
+//! contract_unpause           {"tournament_id": string | null}
+//! tournament_team_register   {"tournament_id": string, "captain_id": string, "name": string,
+//!                             "members": [string], "shares": [number]}
+//! tournament_team_update     {"tournament_id": string, "captain_id": string, "name": string,
+//!                             "members": [string], "shares": [number]}
+//! tournament_allowlist_update    {"tournament_id": string, "added": [string], "removed": [string]}
+//! tournament_invite_codes_update {"tournament_id": string, "added": [string], "removed": [string],
+//!                                 "single_use"?: bool}
+//! tournament_entry_reject    {"tournament_id": string, "player_id": string, "reason": string}
+//! tournament_sponsor         {"tournament_id": string, "sponsor_id": string, "amount": string}
+//! tournament_guarantee       {"tournament_id": string, "owner_id": string, "amount": string}
+//! tournament_guarantee_settle {"tournament_id": string, "owner_id": string, "covered": string, "returned": string}
+//! tournament_payout_tier_lock {"tournament_id": string, "entrants": number, "tier": number | null}
+//! ```
+//!
+//! The amounts are yoctoNEAR strings, JSON numbers lose the precision above 2^53 in JS readers.
+//!
+//! The version 2.0.0 replaced 1.0.0 and nothing logs the version 1.0.0 events anymore, only
+//! `crate::event_parser` reads them for the logs of the old deployments. The 1.0.0 events are
+//! named as in 2.0.0 without the added fields (`owner_id`, `winners`...), their `rewarded_amount`
+//! is a JSON number. They use the `mf1` standard name the standard had before it was named `tournament`.
+
+use crate::event::{NearEvent, TOURNAMENT_SPEC_VERSION};
 use near_sdk::{AccountId};
-use near_sdk::json_types::{U128};
+use near_sdk::json_types::{U64, U128, Base64VecU8};
 use near_sdk::serde::{Serialize};
 
 /// Enum that represents the data type of the EventLog.
@@ -13,13 +61,37 @@ pub enum EventLogVariant<'a> {
     TournamentCreate(&'a [TournamentCreateLog<'a>]),
     TournamentEntrance(&'a [TournamentEntranceLog<'a>]),
     TournamentPrizesReward(&'a [TournamentPrizesRewardLog<'a>]),
+    TournamentUpdate(&'a [TournamentUpdateLog<'a>]),
+    TournamentOwnerPropose(&'a [TournamentOwnerProposeLog<'a>]),
+    TournamentOwnerAccept(&'a [TournamentOwnerAcceptLog<'a>]),
+    ContractOwnerPropose(&'a [ContractOwnerProposeLog<'a>]),
+    ContractOwnerAccept(&'a [ContractOwnerAcceptLog<'a>]),
+    TournamentOwnerCancel(&'a [TournamentOwnerCancelLog<'a>]),
+    ContractOwnerCancel(&'a [ContractOwnerCancelLog<'a>]),
+    TournamentCancel(&'a [TournamentCancelLog<'a>]),
+    TournamentLeave(&'a [TournamentLeaveLog<'a>]),
+    TournamentRefund(&'a [TournamentRefundLog<'a>]),
+    TournamentFeesWithdraw(&'a [TournamentFeesWithdrawLog<'a>]),
+    ContractUpgradeStage(&'a [ContractUpgradeStageLog<'a>]),
+    ContractUpgradeCancel(&'a [ContractUpgradeCancelLog<'a>]),
+    ContractUpgrade(&'a [ContractUpgradeLog<'a>]),
+    ContractPause(&'a [ContractPauseLog<'a>]),
+    ContractUnpause(&'a [ContractUnpauseLog<'a>]),
+    TournamentTeamRegister(&'a [TournamentTeamRegisterLog<'a>]),
+    TournamentTeamUpdate(&'a [TournamentTeamUpdateLog<'a>]),
+    TournamentAllowlistUpdate(&'a [TournamentAllowlistUpdateLog<'a>]),
+    TournamentInviteCodesUpdate(&'a [TournamentInviteCodesUpdateLog<'a>]),
+    TournamentEntryReject(&'a [TournamentEntryRejectLog<'a>]),
+    TournamentSponsor(&'a [TournamentSponsorLog<'a>]),
+    TournamentGuarantee(&'a [TournamentGuaranteeLog<'a>]),
+    TournamentGuaranteeSettle(&'a [TournamentGuaranteeSettleLog<'a>]),
+    TournamentPayoutTierLock(&'a [TournamentPayoutTierLockLog<'a>]),
 }
 
 /// Interface to capture data about an event
 ///
 /// Arguments:
-/// * `standard`: name of standard e.g. nep171
-/// * `version`: e.g. 1.0.0
+/// * `version`: e.g. 2.0.0
 /// * `event`: associate event data
 #[derive(Serialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
@@ -35,6 +107,7 @@ pub(crate) struct EventLog<'a> {
 ///
 /// Arguments
 /// * `tournament_id`: "tournament-1" 
+/// * `owner_id`: "owner.near"
 /// * `players_number`: 8
 /// * `in_price`: "100000"
 #[must_use]
@@ -42,7 +115,8 @@ pub(crate) struct EventLog<'a> {
 #[serde(crate = "near_sdk::serde")]
 pub struct TournamentCreateLog<'a> {    
     pub tournament_id: &'a String,
-    pub players_number: &'a u8,
+    pub owner_id: &'a AccountId,
+    pub players_number: &'a u32,
     pub in_price:&'a U128,
 }
 
@@ -52,7 +126,7 @@ impl TournamentCreateLog<'_> {
     }
     
     pub fn emit_many(data: &[TournamentCreateLog<'_>]) {
-        new_mf1_v1(EventLogVariant::TournamentCreate(data)).emit()
+        new_tournament_event(EventLogVariant::TournamentCreate(data)).emit()
     }
 }
 
@@ -61,11 +135,14 @@ impl TournamentCreateLog<'_> {
 /// Arguments
 /// * `partisipator_id`: "partisipator.near"
 /// * `tournament_id`: "tournament-1"
+/// * `payer_id`: "sponsor.near", only logged when another account paid for the player
 #[derive(Serialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TournamentEntranceLog<'a> { 
     pub partisipator_id:&'a AccountId,
     pub tournament_id:&'a String,    
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payer_id: Option<&'a AccountId>,
 } 
 
 impl TournamentEntranceLog<'_> {
@@ -74,7 +151,7 @@ impl TournamentEntranceLog<'_> {
     }
     
     pub fn emit_many(data: &[TournamentEntranceLog<'_>]) {
-        new_mf1_v1(EventLogVariant::TournamentEntrance(data)).emit()
+        new_tournament_event(EventLogVariant::TournamentEntrance(data)).emit()
     }
 }
 
@@ -84,27 +161,623 @@ impl TournamentEntranceLog<'_> {
 /// Arguments
 /// * `tournament_id`: "tournament-1"
 /// * `rewarded_amount`: "100000000"
+/// * `winners`: [{"place": 1, "account_id": "winner.near", "amount": "100000000"}]
 #[derive(Serialize,  Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TournamentPrizesRewardLog<'a> {     
     pub tournament_id:&'a String,    
-    pub rewarded_amount:&'a u128,
+    pub rewarded_amount:&'a U128,
+    pub winners:&'a [TournamentWinnerLog],
 } 
 
+/// The prize of a winner in the tournament prize rewarding
+///
+/// Arguments
+/// * `place`: 1
+/// * `account_id`: "winner.near"
+/// * `amount`: "100000000"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentWinnerLog {
+    pub place: u32,
+    pub account_id: AccountId,
+    pub amount: U128,
+}
+
 impl TournamentPrizesRewardLog<'_> {
     pub fn emit(self) {
         Self::emit_many(&[self])
     }
     
     pub fn emit_many(data: &[TournamentPrizesRewardLog<'_>]) {
-        new_mf1_v1(EventLogVariant::TournamentPrizesReward(data)).emit()
+        new_tournament_event(EventLogVariant::TournamentPrizesReward(data)).emit()
+    }
+}
+
+/// An event log to capture tournament metadata changes
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `updated_fields`: ["name", "in_price"]
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentUpdateLog<'a> {
+    pub tournament_id:&'a String,
+    pub updated_fields:&'a [&'a str],
+}
+
+impl TournamentUpdateLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentUpdateLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentUpdate(data)).emit()
+    }
+}
+
+/// An event log to capture the proposal of a new tournament owner
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "new_owner.near"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentOwnerProposeLog<'a> {
+    pub tournament_id:&'a String,
+    pub old_owner_id:&'a AccountId,
+    pub new_owner_id:&'a AccountId,
+}
+
+impl TournamentOwnerProposeLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentOwnerProposeLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentOwnerPropose(data)).emit()
+    }
+}
+
+/// An event log to capture the new tournament owner accepting the ownership
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "new_owner.near"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentOwnerAcceptLog<'a> {
+    pub tournament_id:&'a String,
+    pub old_owner_id:&'a AccountId,
+    pub new_owner_id:&'a AccountId,
+}
+
+impl TournamentOwnerAcceptLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentOwnerAcceptLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentOwnerAccept(data)).emit()
+    }
+}
+
+/// An event log to capture the proposal of a new contract owner
+///
+/// Arguments
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "new_owner.near"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractOwnerProposeLog<'a> {
+    pub old_owner_id:&'a AccountId,
+    pub new_owner_id:&'a AccountId,
+}
+
+impl ContractOwnerProposeLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[ContractOwnerProposeLog<'_>]) {
+        new_tournament_event(EventLogVariant::ContractOwnerPropose(data)).emit()
+    }
+}
+
+/// An event log to capture the new contract owner accepting the ownership
+///
+/// Arguments
+/// * `old_owner_id`: "owner.near"
+/// * `new_owner_id`: "new_owner.near"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractOwnerAcceptLog<'a> {
+    pub old_owner_id:&'a AccountId,
+    pub new_owner_id:&'a AccountId,
+}
+
+impl ContractOwnerAcceptLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[ContractOwnerAcceptLog<'_>]) {
+        new_tournament_event(EventLogVariant::ContractOwnerAccept(data)).emit()
+    }
+}
+
+/// An event log to capture the owner withdrawing the ownership proposal of the tournament
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `old_owner_id`: "owner.near", the owner keeping the tournament
+/// * `new_owner_id`: "new_owner.near", the account the ownership was proposed to
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentOwnerCancelLog<'a> {
+    pub tournament_id:&'a String,
+    pub old_owner_id:&'a AccountId,
+    pub new_owner_id:&'a AccountId,
+}
+
+impl TournamentOwnerCancelLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
     }
+
+    pub fn emit_many(data: &[TournamentOwnerCancelLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentOwnerCancel(data)).emit()
+    }
+}
+
+/// An event log to capture the owner withdrawing the ownership proposal of the contract
+///
+/// Arguments
+/// * `old_owner_id`: "owner.near", the owner keeping the contract
+/// * `new_owner_id`: "new_owner.near", the account the ownership was proposed to
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractOwnerCancelLog<'a> {
+    pub old_owner_id:&'a AccountId,
+    pub new_owner_id:&'a AccountId,
+}
+
+impl ContractOwnerCancelLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[ContractOwnerCancelLog<'_>]) {
+        new_tournament_event(EventLogVariant::ContractOwnerCancel(data)).emit()
+    }
+}
+
+/// An event log to capture tournament cancellation
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `refunded_amount`: "100000000"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentCancelLog<'a> {
+    pub tournament_id:&'a String,
+    pub refunded_amount:&'a U128,
 }
 
-fn new_mf1<'a>(version: &'static str, event: EventLogVariant<'a>) -> NearEvent<'a> {
-  NearEvent::Mf1(EventLog { version, event })
+impl TournamentCancelLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentCancelLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentCancel(data)).emit()
+    }
+}
+
+/// An event log to capture a player leaving the tournament
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `player_id`: "player.near"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentLeaveLog<'a> {
+    pub tournament_id:&'a String,
+    pub player_id:&'a AccountId,
+}
+
+impl TournamentLeaveLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentLeaveLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentLeave(data)).emit()
+    }
+}
+
+/// An event log to capture an entrance deposit refund
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `account_id`: "player.near"
+/// * `amount`: "100000"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentRefundLog<'a> {
+    pub tournament_id:&'a String,
+    pub account_id:&'a AccountId,
+    pub amount:&'a U128,
+}
+
+impl TournamentRefundLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentRefundLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentRefund(data)).emit()
+    }
+}
+
+/// An event log to capture the owner withdrawing the rest of the prize fond
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `owner_id`: "owner.near"
+/// * `amount`: "100000"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentFeesWithdrawLog<'a> {
+    pub tournament_id:&'a String,
+    pub owner_id:&'a AccountId,
+    pub amount:&'a U128,
+}
+
+impl TournamentFeesWithdrawLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentFeesWithdrawLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentFeesWithdraw(data)).emit()
+    }
+}
+
+/// An event log to capture staging a new code of the contract
+///
+/// Arguments
+/// * `code_hash`: base64-encoded sha256 hash of the code
+/// * `apply_after`: "1650000000000000000"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractUpgradeStageLog<'a> {
+    pub code_hash:&'a Base64VecU8,
+    pub apply_after:&'a U64,
+}
+
+impl ContractUpgradeStageLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[ContractUpgradeStageLog<'_>]) {
+        new_tournament_event(EventLogVariant::ContractUpgradeStage(data)).emit()
+    }
+}
+
+/// An event log to capture dropping the staged code of the contract
+///
+/// Arguments
+/// * `code_hash`: base64-encoded sha256 hash of the code
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractUpgradeCancelLog<'a> {
+    pub code_hash:&'a Base64VecU8,
+}
+
+impl ContractUpgradeCancelLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[ContractUpgradeCancelLog<'_>]) {
+        new_tournament_event(EventLogVariant::ContractUpgradeCancel(data)).emit()
+    }
+}
+
+/// An event log to capture deploying a new code of the contract
+///
+/// Arguments
+/// * `code_hash`: base64-encoded sha256 hash of the code
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractUpgradeLog<'a> {
+    pub code_hash:&'a Base64VecU8,
+}
+
+impl ContractUpgradeLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[ContractUpgradeLog<'_>]) {
+        new_tournament_event(EventLogVariant::ContractUpgrade(data)).emit()
+    }
+}
+
+/// An event log to capture pausing the contract (no `tournament_id`) or a tournament
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1" or null
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractPauseLog<'a> {
+    pub tournament_id: Option<&'a String>,
+}
+
+impl ContractPauseLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[ContractPauseLog<'_>]) {
+        new_tournament_event(EventLogVariant::ContractPause(data)).emit()
+    }
+}
+
+/// An event log to capture unpausing the contract (no `tournament_id`) or a tournament
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1" or null
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ContractUnpauseLog<'a> {
+    pub tournament_id: Option<&'a String>,
+}
+
+impl ContractUnpauseLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[ContractUnpauseLog<'_>]) {
+        new_tournament_event(EventLogVariant::ContractUnpause(data)).emit()
+    }
+}
+
+/// An event log to capture a captain registering a team
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `captain_id`: "captain.near"
+/// * `name`: "Team"
+/// * `members`: ["captain.near", "member.near"]
+/// * `shares`: [1, 1]
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentTeamRegisterLog<'a> {
+    pub tournament_id:&'a String,
+    pub captain_id:&'a AccountId,
+    pub name:&'a String,
+    pub members:&'a [AccountId],
+    pub shares:&'a [u16],
+}
+
+impl TournamentTeamRegisterLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentTeamRegisterLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentTeamRegister(data)).emit()
+    }
+}
+
+/// An event log to capture a captain changing the team, the team is logged as it is after the change
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `captain_id`: "captain.near"
+/// * `name`: "Team"
+/// * `members`: ["captain.near", "member.near"]
+/// * `shares`: [1, 1]
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentTeamUpdateLog<'a> {
+    pub tournament_id:&'a String,
+    pub captain_id:&'a AccountId,
+    pub name:&'a String,
+    pub members:&'a [AccountId],
+    pub shares:&'a [u16],
+}
+
+impl TournamentTeamUpdateLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentTeamUpdateLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentTeamUpdate(data)).emit()
+    }
+}
+
+/// An event log to capture the owner changing the allowlist of the tournament
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `added`: ["player.near"]
+/// * `removed`: []
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentAllowlistUpdateLog<'a> {
+    pub tournament_id:&'a String,
+    pub added:&'a [AccountId],
+    pub removed:&'a [AccountId],
+}
+
+impl TournamentAllowlistUpdateLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentAllowlistUpdateLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentAllowlistUpdate(data)).emit()
+    }
+}
+
+/// An event log to capture the owner changing the invite codes of the tournament, only the hashes are logged
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `added`: ["<base64 sha256 of the code>"]
+/// * `removed`: []
+/// * `single_use`: true, only when codes are added
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentInviteCodesUpdateLog<'a> {
+    pub tournament_id:&'a String,
+    pub added:&'a [Base64VecU8],
+    pub removed:&'a [Base64VecU8],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub single_use: Option<bool>,
+}
+
+impl TournamentInviteCodesUpdateLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentInviteCodesUpdateLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentInviteCodesUpdate(data)).emit()
+    }
+}
+
+/// An event log to capture the entrance of the gated tournament rejected after the gate checks,
+/// the deposit is refunded to the payer with the `tournament_refund` event
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `player_id`: "player.near"
+/// * `reason`: "player.near didn't pass the entry gates"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentEntryRejectLog<'a> {
+    pub tournament_id:&'a String,
+    pub player_id:&'a AccountId,
+    pub reason:&'a String,
+}
+
+impl TournamentEntryRejectLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentEntryRejectLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentEntryReject(data)).emit()
+    }
+}
+
+/// An event log to capture a contribution to the prize fond of the tournament
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `sponsor_id`: "sponsor.near"
+/// * `amount`: "1000000"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentSponsorLog<'a> {
+    pub tournament_id:&'a String,
+    pub sponsor_id:&'a AccountId,
+    pub amount:&'a U128,
+}
+
+impl TournamentSponsorLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentSponsorLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentSponsor(data)).emit()
+    }
+}
+
+/// An event log to capture the prize fond guaranteed by the owner of the tournament
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `owner_id`: "owner.near"
+/// * `amount`: "1000000"
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentGuaranteeLog<'a> {
+    pub tournament_id:&'a String,
+    pub owner_id:&'a AccountId,
+    pub amount:&'a U128,
+}
+
+impl TournamentGuaranteeLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentGuaranteeLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentGuarantee(data)).emit()
+    }
+}
+
+/// An event log to capture how the guarantee was used when the tournament was completed or cancelled
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `owner_id`: "owner.near"
+/// * `covered`: "400000", the part added to the prize fond
+/// * `returned`: "600000", the part returned to the owner
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentGuaranteeSettleLog<'a> {
+    pub tournament_id:&'a String,
+    pub owner_id:&'a AccountId,
+    pub covered:&'a U128,
+    pub returned:&'a U128,
+}
+
+impl TournamentGuaranteeSettleLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentGuaranteeSettleLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentGuaranteeSettle(data)).emit()
+    }
+}
+
+/// An event log to capture the payout tier locked when the tournament is paid out or cancelled
+///
+/// Arguments
+/// * `tournament_id`: "tournament-1"
+/// * `entrants`: 4
+/// * `tier`: 1, the index of the tier in the `payout_tiers`, null when the base prizes are paid
+#[derive(Serialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentPayoutTierLockLog<'a> {
+    pub tournament_id:&'a String,
+    pub entrants:u32,
+    pub tier:Option<u8>,
+}
+
+impl TournamentPayoutTierLockLog<'_> {
+    pub fn emit(self) {
+        Self::emit_many(&[self])
+    }
+
+    pub fn emit_many(data: &[TournamentPayoutTierLockLog<'_>]) {
+        new_tournament_event(EventLogVariant::TournamentPayoutTierLock(data)).emit()
+    }
 }
 
-fn new_mf1_v1(event: EventLogVariant) -> NearEvent {
-  new_mf1("1.0.0", event)
+fn new_tournament_event(event: EventLogVariant) -> NearEvent {
+  NearEvent::Tournament(EventLog { version: TOURNAMENT_SPEC_VERSION, event })
 }
@@ -0,0 +1,303 @@
+//! Entries gated on holding an NFT or a fungible token balance.
+//!
+//! `participate_tournament` of a gated tournament checks the entrance as usual, keeps the deposit
+//! and calls the views of the gate contracts. The seat is taken in `on_entry_gates_checked` once
+//! the player passes one of the gates, otherwise the deposit is refunded to the payer. The entrance
+//! is checked again in the callback since the tournament could fill up in the meantime.
+
+use crate::*;
+use crate::tournament::events::{TournamentEntryRejectLog, TournamentRefundLog};
+use crate::tournament::internal::Seat;
+use near_sdk::{Balance, Gas, Promise, PromiseResult};
+use serde_json::json;
+
+/// Gas attached to the view call of each gate
+const GAS_FOR_GATE_VIEW: Gas = Gas(10_000_000_000_000);
+
+/// Gas attached to the callback taking the seat
+const GAS_FOR_ON_ENTRY_GATES_CHECKED: Gas = Gas(25_000_000_000_000);
+
+pub trait TournamentContractGates {
+    //take the seat of the player who passed one of the gates, refund the deposit to the payer otherwise
+    fn on_entry_gates_checked(
+        &mut self,
+        tournament_id: TournamentId,
+        player_id: AccountId,
+        payer_id: AccountId,
+        deposit: U128,
+        invite_code: Option<String>,
+        gates: Vec<EntryGate>,
+    ) -> bool;
+}
+
+impl EntryGate {
+    //the view call checking the account
+    fn view_call(&self, account_id: &AccountId) -> Promise {
+        let (contract_id, method_name, args) = match self {
+            EntryGate::Nft { contract_id } => (contract_id, "nft_tokens_for_owner", json!({ "account_id": account_id, "limit": 1 })),
+            EntryGate::Ft { contract_id, .. } => (contract_id, "ft_balance_of", json!({ "account_id": account_id })),
+        };
+
+        Promise::new(contract_id.clone()).function_call(
+            method_name.to_string(),
+            args.to_string().into_bytes(),
+            0,
+            GAS_FOR_GATE_VIEW,
+        )
+    }
+
+    //checks the value returned by the view call
+    pub(crate) fn is_passed(&self, result: &[u8]) -> bool {
+        match self {
+            EntryGate::Nft { .. } => matches!(
+                serde_json::from_slice::<Vec<serde_json::Value>>(result),
+                Ok(tokens) if !tokens.is_empty()
+            ),
+            EntryGate::Ft { min_balance, .. } => matches!(
+                serde_json::from_slice::<U128>(result),
+                Ok(balance) if balance.0 >= min_balance.0
+            ),
+        }
+    }
+}
+
+impl TournamentContract {
+    //call the views of the gates and finish the entrance in the callback
+    pub(crate) fn internal_check_gates(
+        &self,
+        tournament_id: &TournamentId,
+        gates: &[EntryGate],
+        player_id: &AccountId,
+        payer_id: &AccountId,
+        deposit: Balance,
+        invite_code: Option<String>,
+    ) {
+        let mut views = gates.iter().map(|gate| gate.view_call(player_id));
+        let first_view = views.next().expect("No entry gates");
+        let all_views = views.fold(first_view, |all_views, view| all_views.and(view));
+
+        all_views.then(Promise::new(env::current_account_id()).function_call(
+            "on_entry_gates_checked".to_string(),
+            json!({
+                "tournament_id": tournament_id,
+                "player_id": player_id,
+                "payer_id": payer_id,
+                "deposit": U128(deposit),
+                "invite_code": invite_code,
+                "gates": gates,
+            }).to_string().into_bytes(),
+            0,
+            GAS_FOR_ON_ENTRY_GATES_CHECKED,
+        ));
+    }
+
+    //check the entrance again with the results of the gate views
+    fn internal_check_gated_entrance(
+        &self,
+        tournament_id: &TournamentId,
+        player_id: &AccountId,
+        deposit: Balance,
+        invite_code: Option<&String>,
+        gates: &[EntryGate],
+    ) -> Result<(Tournament, TournamentMetadata, Option<CryptoHash>), String> {
+        //the results come in the order of the gates, one passed gate is enough
+        let passed = gates.iter().enumerate().any(|(index, gate)| match env::promise_result(index as u64) {
+            PromiseResult::Successful(result) => gate.is_passed(&result),
+            _ => false,
+        });
+        ensure!(passed, "{} didn't pass the entry gates", player_id);
+
+        let tournament = self.tournaments_by_id.get(tournament_id).ok_or_else(|| "No tournament".to_string())?;
+        let metadata = self.tournament_metadata_by_id.get(tournament_id).unwrap();
+
+        self.internal_check_entrance(tournament_id, &tournament, &metadata, player_id, deposit)?;
+        let spent_code = self.internal_check_access(tournament_id, &metadata, player_id, invite_code)?;
+
+        Ok((tournament, metadata, spent_code))
+    }
+}
+
+impl TournamentContractGates for TournamentContract {
+    //take the seat of the player who passed one of the gates, refund the deposit to the payer otherwise
+    fn on_entry_gates_checked(
+        &mut self,
+        tournament_id: TournamentId,
+        player_id: AccountId,
+        payer_id: AccountId,
+        deposit: U128,
+        invite_code: Option<String>,
+        gates: Vec<EntryGate>,
+    ) -> bool {
+        //the callback doesn't panic, the deposit would be kept by the contract
+        match self.internal_check_gated_entrance(&tournament_id, &player_id, deposit.0, invite_code.as_ref(), &gates) {
+            Ok((mut tournament, metadata, spent_code)) => {
+                self.internal_take_seat(&tournament_id, &mut tournament, &metadata, Seat {
+                    account_id: &player_id,
+                    payer_id: &payer_id,
+                    deposit: deposit.0,
+                    spent_code,
+                });
+                true
+            }
+            Err(reason) => {
+                TournamentEntryRejectLog{
+                    tournament_id: &tournament_id,
+                    player_id: &player_id,
+                    reason: &reason,
+                }.emit();
+
+                TournamentRefundLog{
+                    tournament_id: &tournament_id,
+                    account_id: &payer_id,
+                    amount: &deposit,
+                }.emit();
+
+                Promise::new(payer_id).transfer(deposit.0);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_parser::*;
+    use crate::tournament::testing::*;
+    use near_sdk::test_utils::accounts;
+
+    const TOURNAMENT_ID: &str = "tournament-1";
+
+    fn account(account_id: &str) -> AccountId {
+        account_id.parse().unwrap()
+    }
+
+    fn nft_gate() -> EntryGate {
+        EntryGate::Nft { contract_id: account("guild-nft.near") }
+    }
+
+    fn ft_gate() -> EntryGate {
+        EntryGate::Ft { contract_id: account("guild-ft.near"), min_balance: U128(1_000) }
+    }
+
+    //a tournament of accounts(0) for two players admitting the holders of the guild NFT or FT
+    fn setup() -> TournamentContract {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 2, IN_PRICE);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            gates: Some(vec![nft_gate(), ft_gate()]),
+            ..Default::default()
+        });
+        contract
+    }
+
+    //finish the entrance of the player who paid the exact in price with the results of the gate views
+    fn finish_entrance(contract: &mut TournamentContract, player_id: AccountId, results: Vec<PromiseResult>) -> bool {
+        set_context_with_results(accounts(0), results);
+        contract.on_entry_gates_checked(
+            TOURNAMENT_ID.to_string(),
+            player_id.clone(),
+            player_id,
+            U128(IN_PRICE),
+            None,
+            vec![nft_gate(), ft_gate()],
+        )
+    }
+
+    #[test]
+    fn gate_results() {
+        assert!(nft_gate().is_passed(br#"[{"token_id": "1", "owner_id": "alice.near"}]"#));
+        assert!(!nft_gate().is_passed(b"[]"));
+        assert!(ft_gate().is_passed(br#""1000""#));
+        assert!(!ft_gate().is_passed(br#""999""#));
+        assert!(!ft_gate().is_passed(b"not json"));
+    }
+
+    #[test]
+    fn participate_waits_for_gate_checks() {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+
+        //the seat is taken in the callback
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(2)));
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(0));
+    }
+
+    #[test]
+    fn holder_takes_seat() {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+
+        let results = vec![PromiseResult::Successful(b"[]".to_vec()), PromiseResult::Successful(br#""5000""#.to_vec())];
+        assert!(finish_entrance(&mut contract, accounts(1), results));
+
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(1)));
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(IN_PRICE));
+        assert_eq!(logged_events(), vec![
+            TournamentEventData::TournamentEntrance(vec![TournamentEntranceData {
+                partisipator_id: accounts(1),
+                tournament_id: TOURNAMENT_ID.to_string(),
+                payer_id: None,
+            }]),
+        ]);
+    }
+
+    #[test]
+    fn non_holder_is_refunded() {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+
+        let results = vec![PromiseResult::Successful(b"[]".to_vec()), PromiseResult::Failed];
+        assert!(!finish_entrance(&mut contract, accounts(1), results));
+
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(2)));
+        assert_eq!(logged_events(), vec![
+            TournamentEventData::TournamentEntryReject(vec![TournamentEntryRejectData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                player_id: accounts(1),
+                reason: format!("{} didn't pass the entry gates", accounts(1)),
+            }]),
+            TournamentEventData::TournamentRefund(vec![TournamentRefundData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                account_id: accounts(1),
+                amount: U128(IN_PRICE),
+            }]),
+        ]);
+    }
+
+    #[test]
+    fn full_tournament_refunds_holder() {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+        join(&mut contract, TOURNAMENT_ID, accounts(3));
+
+        let holder = || vec![PromiseResult::Successful(br#"[{"token_id": "1"}]"#.to_vec()), PromiseResult::Failed];
+        assert!(finish_entrance(&mut contract, accounts(1), holder()));
+        assert!(finish_entrance(&mut contract, accounts(2), holder()));
+
+        //the last seat was taken while the gates of the third player were checked
+        assert!(!finish_entrance(&mut contract, accounts(3), holder()));
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Deposit is too small")]
+    fn participate_gated_with_small_deposit() {
+        let mut contract = setup();
+
+        set_context(accounts(1), IN_PRICE - 1);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Entry gates are for solo tournaments only")]
+    fn gates_of_team_tournament() {
+        let mut contract = new_contract();
+        create_team_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 2, 2);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            gates: Some(vec![nft_gate()]),
+            ..Default::default()
+        });
+    }
+}
@@ -1,8 +1,12 @@
 use crate::*;
-use near_sdk::{CryptoHash};
+use crate::tournament::validation::{check_basis_points, check_payout_tiers, check_schedule};
+use crate::payout::{compute_payout, split_prize, PayoutPlan, PrizeTransfer};
+use crate::tournament::events::{TournamentEntranceLog, TournamentGuaranteeSettleLog, TournamentLeaveLog, TournamentPayoutTierLockLog, TournamentRefundLog};
+use near_sdk::{Balance, CryptoHash, Promise};
+use near_sdk::collections::UnorderedMap;
 
 //used to generate a unique prefix in our storage collections (this is to avoid data collisions)
-pub(crate) fn hash_tournament_id(tournament_id: &TournamentId, shift: &String) -> CryptoHash {
+pub(crate) fn hash_tournament_id(tournament_id: &TournamentId, shift: &str) -> CryptoHash {
     //get the default hash
     let mut hash = CryptoHash::default();
     
@@ -12,47 +16,575 @@ pub(crate) fn hash_tournament_id(tournament_id: &TournamentId, shift: &String) -
     hash
 }
 
-//calculates the percents from the amount
-pub(crate) fn percent_calculation ( &percent_value: &u128, &amount: &u128)-> u128 {    
-    let mut percent_amount: u128 = (percent_value * amount)/100;    
-    let reminder = (percent_value * amount)%100;                
-        if reminder!=0{
-            if ((amount * 10 )/reminder) > 4 {
-                percent_amount+=1;
-            } 
-        }        
-    return percent_amount;        
+//used to generate a unique prefix in our storage collections (this is to avoid data collisions)
+pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
+    //get the default hash
+    let mut hash = CryptoHash::default();
+    
+    //we hash the account ID and return it
+    hash.copy_from_slice(&env::sha256(account_id.as_str().as_bytes()));
+    
+    hash
+}
+
+//the stored hash of the invite code
+pub(crate) fn hash_invite_code(invite_code: &str) -> CryptoHash {
+    let mut hash = CryptoHash::default();
+    
+    hash.copy_from_slice(&env::sha256(invite_code.as_bytes()));
+    
+    hash
+}
+
+//checks the places of the basis points map and that it doesn't distribute more than the prize fond
+pub(crate) fn assert_valid_basis_points(basis_points_map: &HashMap<u32,u16>, players_number: u32) {
+    if let Err(message) = check_basis_points(basis_points_map, players_number) {
+        panic!("{}", message);
+    }
+}
+
+//checks the entrants ranges and the prizes of the payout tiers
+pub(crate) fn assert_valid_payout_tiers(payout_tiers: &[PayoutTier], players_number: u32) {
+    if let Err(message) = check_payout_tiers(payout_tiers, players_number) {
+        panic!("{}", message);
+    }
+}
+
+//get the index of the payout tier with the entrants in its range
+fn match_payout_tier(payout_tiers: &[PayoutTier], entrants: u32) -> Option<u8> {
+    payout_tiers.iter()
+        .position(|tier| tier.min_entrants <= entrants && entrants <= tier.max_entrants)
+        .map(|index| index as u8)
+}
+
+//turns an empty string into None to clear optional fields
+pub(crate) fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+//checks the tournament ends after it starts
+pub(crate) fn assert_valid_schedule(starts_at: &Option<U64>, ends_at: &Option<U64>) {
+    if let Err(message) = check_schedule(starts_at, ends_at) {
+        panic!("{}", message);
+    }
+}
+
+//The checked seat taken in the tournament, paid from the deposit of the payer
+pub(crate) struct Seat<'a> {
+    pub account_id: &'a AccountId,
+    pub payer_id: &'a AccountId,
+    pub deposit: Balance,
+    //the single use invite code the seat is taken with
+    pub spent_code: Option<CryptoHash>,
 }
 
 impl TournamentContract {
-    //add prize values in percents to the tournament
+    //check neither the contract nor the tournament is paused
+    pub(crate) fn internal_assert_not_paused(&self, tournament_id: &TournamentId) {
+        assert!(!self.paused, "Contract is paused");
+        assert!(!self.paused_tournaments.contains(tournament_id), "Tournament is paused");
+    }
+    
+    //check the account is on the allowlist of the tournament
+    pub(crate) fn internal_is_allowed(&self, tournament_id: &TournamentId, account_id: &AccountId) -> bool {
+        matches!(self.allowed_per_tournament.get(tournament_id), Some(allowed_set) if allowed_set.contains(account_id))
+    }
+    
+    //panics when the account isn't on the allowlist of the tournament
+    pub(crate) fn internal_assert_allowed(&self, tournament_id: &TournamentId, account_id: &AccountId) {
+        assert!(self.internal_is_allowed(tournament_id, account_id), "{} is not on the allowlist", account_id);
+    }
+    
+    //check the account can take a seat in the tournament paying the deposit
+    pub(crate) fn internal_check_entrance(
+        &self,
+        tournament_id: &TournamentId,
+        tournament: &Tournament,
+        metadata: &TournamentMetadata,
+        account_id: &AccountId,
+        deposit: Balance,
+    ) -> Result<(), String> {
+        //check activeness of the tournament
+        ensure!(tournament.state == TournamentState::Open, "Tournament is inactive");
+        
+        //entries are blocked while paused
+        ensure!(!self.paused, "Contract is paused");
+        ensure!(!self.paused_tournaments.contains(tournament_id), "Tournament is paused");
+        
+        //check the registration is still open
+        if let Some(starts_at) = metadata.starts_at {
+            ensure!(env::block_timestamp() < starts_at.0, "Registration is closed");
+        }
+        
+        //Check there are some free playses for the players in the tournament
+        ensure!(
+            metadata.players_number > self.internal_get_players_number_in_tournament(tournament_id),
+            "Tournament is already full of players"
+        );
+        
+        //check the is enouph deposit attached to players account
+        ensure!(deposit >= metadata.in_price, "Deposit is too small. Attached: {}, Required: {}", deposit, metadata.in_price);
+        
+        //check for double participation
+        ensure!(
+            !matches!(self.players_per_tournament.get(tournament_id), Some(players_set) if players_set.contains(account_id)),
+            "Already in the tournament"
+        );
+        
+        Ok(())
+    }
+    
+    //check the account can enter the tournament in its access mode,
+    //returns the hash of the single use invite code to spend with the entrance
+    pub(crate) fn internal_check_access(
+        &self,
+        tournament_id: &TournamentId,
+        metadata: &TournamentMetadata,
+        account_id: &AccountId,
+        invite_code: Option<&String>,
+    ) -> Result<Option<CryptoHash>, String> {
+        match metadata.access {
+            TournamentAccess::Public => Ok(None),
+            TournamentAccess::Allowlist => {
+                ensure!(self.internal_is_allowed(tournament_id, account_id), "{} is not on the allowlist", account_id);
+                Ok(None)
+            }
+            TournamentAccess::InviteCode => {
+                let invite_code = invite_code.ok_or_else(|| "Invite code is required".to_string())?;
+                let code_hash = hash_invite_code(invite_code);
+                
+                let single_use = self.invite_codes_per_tournament.get(tournament_id)
+                    .and_then(|codes_map| codes_map.get(&code_hash))
+                    .ok_or_else(|| "Invalid invite code".to_string())?;
+                
+                Ok(if single_use { Some(code_hash) } else { None })
+            }
+        }
+    }
+    
+    //enter the open tournament, the payer pays the in price from the attached deposit.
+    //The seat of a gated tournament is taken in the callback of the gate checks.
+    pub(crate) fn internal_enter_tournament(
+        &mut self,
+        tournament_id: &TournamentId,
+        tournament: &mut Tournament,
+        metadata: &TournamentMetadata,
+        account_id: &AccountId,
+        payer_id: &AccountId,
+        invite_code: Option<String>,
+    ) {
+        let attached_deposit: Balance = env::attached_deposit();
+        
+        //the checks are done before the gate checks as well, so the deposit isn't taken in vain
+        let spent_code = self.internal_check_entrance(tournament_id, tournament, metadata, account_id, attached_deposit)
+            .and_then(|_| self.internal_check_access(tournament_id, metadata, account_id, invite_code.as_ref()))
+            .unwrap_or_else(|message| panic!("{}", message));
+        
+        if let Some(gates) = &metadata.gates {
+            self.internal_check_gates(tournament_id, gates, account_id, payer_id, attached_deposit, invite_code);
+        } else {
+            self.internal_take_seat(tournament_id, tournament, metadata, Seat {
+                account_id,
+                payer_id,
+                deposit: attached_deposit,
+                spent_code,
+            });
+        }
+    }
+    
+    //free the seat of the player before the registration closes, the deposit goes back to the payer
+    pub(crate) fn internal_leave_tournament(&mut self, tournament_id: &TournamentId, account_id: &AccountId) {
+        let mut tournament = self.tournaments_by_id.get(tournament_id).expect("No tournament");
+        
+        //check the tournament is active
+        assert_eq!(tournament.state, TournamentState::Open, "Tournament is inactive");
+        
+        let metadata = self.tournament_metadata_by_id.get(tournament_id).unwrap();
+        
+        //without the start time the play can begin any moment, so only the scheduled tournaments can be left
+        let starts_at = metadata.starts_at.expect("Tournament without start time can't be left");
+        assert!(env::block_timestamp() < starts_at.0, "Registration is closed");
+        
+        assert!(self.internal_remove_player_from_tournament(tournament_id, account_id), "Not in the tournament");
+        
+        //the team leaves with its captain
+        if metadata.team_size.is_some() {
+            self.internal_remove_team(tournament_id, account_id);
+        }
+        
+        //decrease the prize fond of tournament 
+        tournament.balance -= metadata.in_price;
+        self.tournaments_by_id.insert(tournament_id, &tournament);
+        
+        self.stats.players_total -= 1;
+        self.stats.locked_balance -= metadata.in_price;
+        
+        let payer_id = self.internal_remove_payer(tournament_id, account_id);
+        
+        TournamentLeaveLog{
+            tournament_id,
+            player_id: account_id,
+        }.emit();
+        
+        //refund the deposit to the account that paid for the seat, nothing was paid for the free seat
+        if metadata.in_price > 0 {
+            Promise::new(payer_id.clone()).transfer(metadata.in_price);
+            
+            TournamentRefundLog{
+                tournament_id,
+                account_id: &payer_id,
+                amount: &U128(metadata.in_price),
+            }.emit();
+        }
+    }
+    
+    //take the seat of the checked account, the deposit above the in price is refunded to the payer
+    pub(crate) fn internal_take_seat(
+        &mut self,
+        tournament_id: &TournamentId,
+        tournament: &mut Tournament,
+        metadata: &TournamentMetadata,
+        seat: Seat,
+    ) {
+        let Seat { account_id, payer_id, deposit, spent_code } = seat;
+        
+        //a single use invite code can't be used again
+        if let Some(code_hash) = spent_code {
+            if let Some(mut codes_map) = self.invite_codes_per_tournament.get(tournament_id) {
+                codes_map.remove(&code_hash);
+            }
+        }
+        
+        self.internal_add_player_to_tournament(tournament_id, account_id);
+        
+        //the deposit of a sponsored seat is refunded to the payer
+        let sponsored = payer_id != account_id;
+        if sponsored {
+            self.internal_add_payer(tournament_id, account_id, payer_id);
+        }
+        
+        //save the prize fond balanse of the tournament 
+        tournament.balance+=metadata.in_price;
+        self.tournaments_by_id.insert(tournament_id, tournament);
+        
+        //count the entrance and the locked deposit
+        self.stats.players_total += 1;
+        self.stats.locked_balance += metadata.in_price;
+        
+        TournamentEntranceLog{
+            partisipator_id: account_id,
+            tournament_id,
+            payer_id: if sponsored { Some(payer_id) } else { None },
+        }.emit();
+        
+        //get the refund amount from the deposit - required cost
+        let refund = deposit - metadata.in_price;
+        
+        //if the refund is greater than 1 yocto NEAR, we refund the payer that amount
+        if refund > 1 {
+            Promise::new(payer_id.clone()).transfer(refund);
+        }
+    }
+    
+    //calculate the prizes of the winners from the prize fond of the tournament,
+    //the prizes of the teams are split between the members
+    pub(crate) fn internal_payout_plan(
+        &self,
+        tournament_id: &TournamentId,
+        tournament: &Tournament,
+        winners_map: HashMap<u32,AccountId>,
+    ) -> PayoutPlan<AccountId> {
+        //get prizes values in basis points for the places, the payout tier matching the entrants replaces them
+        let prizes_map = self.winners_basis_points_per_tournament.get(tournament_id).unwrap();
+        let active_payout_tier = self.internal_get_active_payout_tier(tournament_id);
+        let basis_points = |place: u32| match &active_payout_tier {
+            Some((_, tier)) => tier.basis_points_map.get(&place).copied(),
+            None => prizes_map.get(&place),
+        };
+        
+        let mut plan = compute_payout(self.internal_get_prize_pot(tournament_id, tournament), 0, basis_points, winners_map)
+            .unwrap_or_else(|error| panic!("{}", error));
+        
+        let metadata = self.tournament_metadata_by_id.get(tournament_id).unwrap();
+        if metadata.team_size.is_some() {
+            let teams = self.teams_per_tournament.get(tournament_id);
+            
+            let mut transfers = Vec::new();
+            for transfer in plan.transfers {
+                //the winners of the team tournaments are the captains
+                let team = teams.as_ref()
+                    .and_then(|teams| teams.get(&transfer.account_id))
+                    .unwrap_or_else(|| panic!("{} isn't a captain in the tournament", transfer.account_id));
+                
+                for (account_id, amount) in team.members.into_iter().zip(split_prize(transfer.amount, &team.shares)) {
+                    //members without a share get nothing
+                    if amount > 0 {
+                        transfers.push(PrizeTransfer { place: transfer.place, account_id, amount });
+                    }
+                }
+            }
+            plan.transfers = transfers;
+        }
+        
+        plan
+    }
+    
+    //remember the account that paid for the seat of the player
+    pub(crate) fn internal_add_payer(
+        &mut self,
+        tournament_id: &TournamentId,
+        player_id: &AccountId,
+        payer_id: &AccountId,
+    ) {
+        let mut payers_map = self.payers_per_tournament.get(tournament_id).unwrap_or_else(|| {
+            //if the tournament doesn't have any sponsored players, we create a new map
+            LookupMap::new(
+                StorageKey::PayersPerTournamentInner {
+                    //we get a new unique prefix for the collection
+                    tournament_id_hash: hash_tournament_id(tournament_id, "p"),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        
+        payers_map.insert(player_id, payer_id);
+        
+        self.payers_per_tournament.insert(tournament_id, &payers_map);
+    }
+    
+    //get the account that paid for the seat of the player, the player itself if nobody else did
+    pub(crate) fn internal_get_payer(
+        &self,
+        tournament_id: &TournamentId,
+        player_id: &AccountId,
+    ) -> AccountId {
+        self.payers_per_tournament.get(tournament_id)
+            .and_then(|payers_map| payers_map.get(player_id))
+            .unwrap_or_else(|| player_id.clone())
+    }
+    
+    //forget the payer of the player leaving the tournament and return it
+    pub(crate) fn internal_remove_payer(
+        &mut self,
+        tournament_id: &TournamentId,
+        player_id: &AccountId,
+    ) -> AccountId {
+        self.payers_per_tournament.get(tournament_id)
+            .and_then(|mut payers_map| payers_map.remove(player_id))
+            .unwrap_or_else(|| player_id.clone())
+    }
+    
+    //add the contribution of the sponsor to its previous ones
+    pub(crate) fn internal_add_sponsorship(
+        &mut self,
+        tournament_id: &TournamentId,
+        sponsor_id: &AccountId,
+        amount: Balance,
+    ) {
+        let mut sponsors_map = self.sponsors_per_tournament.get(tournament_id).unwrap_or_else(|| {
+            //if the tournament doesn't have any sponsors, we create a new map
+            UnorderedMap::new(
+                StorageKey::SponsorsPerTournamentInner {
+                    //we get a new unique prefix for the collection
+                    tournament_id_hash: hash_tournament_id(tournament_id, "o"),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        
+        let contributed = sponsors_map.get(sponsor_id).unwrap_or(0);
+        sponsors_map.insert(sponsor_id, &(contributed + amount));
+        
+        self.sponsors_per_tournament.insert(tournament_id, &sponsors_map);
+    }
+    
+    //get the sponsors of the tournament with the sums of their contributions
+    pub(crate) fn internal_get_sponsors(&self, tournament_id: &TournamentId) -> Vec<(AccountId, Balance)> {
+        self.sponsors_per_tournament.get(tournament_id)
+            .map(|sponsors_map| sponsors_map.to_vec())
+            .unwrap_or_default()
+    }
+    
+    //get the payout tier with its index, the locked tier or the one matching the number of entrants before the lock
+    pub(crate) fn internal_get_active_payout_tier(&self, tournament_id: &TournamentId) -> Option<(u8, PayoutTier)> {
+        let payout_tiers = self.payout_tiers_per_tournament.get(tournament_id)?;
+        
+        let index = match self.locked_payout_tiers_per_tournament.get(tournament_id) {
+            Some(locked) => locked?,
+            None => match_payout_tier(&payout_tiers, self.internal_get_players_number_in_tournament(tournament_id))?,
+        };
+        
+        payout_tiers.into_iter().nth(index as usize).map(|tier| (index, tier))
+    }
+    
+    //lock the payout tier matching the entrants when the registration closes with the payout or the cancel,
+    //the payout and the views keep using it whatever happens next
+    pub(crate) fn internal_lock_payout_tier(&mut self, tournament_id: &TournamentId) {
+        let payout_tiers = match self.payout_tiers_per_tournament.get(tournament_id) {
+            Some(payout_tiers) => payout_tiers,
+            None => return,
+        };
+        if self.locked_payout_tiers_per_tournament.contains_key(tournament_id) {
+            return;
+        }
+        
+        let entrants = self.internal_get_players_number_in_tournament(tournament_id);
+        let tier = match_payout_tier(&payout_tiers, entrants);
+        self.locked_payout_tiers_per_tournament.insert(tournament_id, &tier);
+        
+        TournamentPayoutTierLockLog{
+            tournament_id,
+            entrants,
+            tier,
+        }.emit();
+    }
+    
+    //the prize fond of the open tournament falling short of the guarantee is topped up to it at payout
+    pub(crate) fn internal_get_prize_pot(&self, tournament_id: &TournamentId, tournament: &Tournament) -> Balance {
+        let guarantee = self.guarantees_per_tournament.get(tournament_id).unwrap_or(0);
+        if tournament.state == TournamentState::Open {
+            std::cmp::max(tournament.balance, guarantee)
+        } else {
+            tournament.balance
+        }
+    }
+    
+    //cover the gap between the prize fond and the guarantee, the rest of the guarantee goes back to the owner
+    pub(crate) fn internal_settle_guarantee(&mut self, tournament_id: &TournamentId, tournament: &mut Tournament, cover: bool) {
+        let guarantee = match self.guarantees_per_tournament.get(tournament_id) {
+            Some(guarantee) => guarantee,
+            None => return,
+        };
+        
+        let covered = if cover { guarantee.saturating_sub(tournament.balance) } else { 0 };
+        let returned = guarantee - covered;
+        
+        //the covered part stays locked in the prize fond
+        tournament.balance += covered;
+        self.stats.locked_balance -= returned;
+        
+        if returned > 0 {
+            Promise::new(tournament.owner_id.clone()).transfer(returned);
+        }
+        
+        TournamentGuaranteeSettleLog{
+            tournament_id,
+            owner_id: &tournament.owner_id,
+            covered: &covered.into(),
+            returned: &returned.into(),
+        }.emit();
+    }
+    
+    //save the team of the captain indexing its members
+    pub(crate) fn internal_add_team(
+        &mut self,
+        tournament_id: &TournamentId,
+        captain_id: &AccountId,
+        team: &Team,
+    ) {
+        let mut teams_map = self.teams_per_tournament.get(tournament_id).unwrap_or_else(|| {
+            //if the tournament doesn't have any teams, we create a new map
+            LookupMap::new(
+                StorageKey::TeamsPerTournamentInner {
+                    //we get a new unique prefix for the collection
+                    tournament_id_hash: hash_tournament_id(tournament_id, "t"),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        let mut members_map = self.team_members_per_tournament.get(tournament_id).unwrap_or_else(|| {
+            LookupMap::new(
+                StorageKey::TeamMembersPerTournamentInner {
+                    tournament_id_hash: hash_tournament_id(tournament_id, "u"),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        
+        //a member can play for one team only
+        for member in team.members.iter() {
+            if let Some(other_captain_id) = members_map.get(member) {
+                assert_eq!(&other_captain_id, captain_id, "{} is already in another team", member);
+            }
+            members_map.insert(member, captain_id);
+        }
+        teams_map.insert(captain_id, team);
+        
+        self.teams_per_tournament.insert(tournament_id, &teams_map);
+        self.team_members_per_tournament.insert(tournament_id, &members_map);
+    }
+    
+    //remove the team of the captain with its members from the indexes
+    pub(crate) fn internal_remove_team(
+        &mut self,
+        tournament_id: &TournamentId,
+        captain_id: &AccountId,
+    ) -> Option<Team> {
+        let mut teams_map = self.teams_per_tournament.get(tournament_id)?;
+        let team = teams_map.remove(captain_id)?;
+        
+        if let Some(mut members_map) = self.team_members_per_tournament.get(tournament_id) {
+            for member in team.members.iter() {
+                members_map.remove(member);
+            }
+        }
+        
+        Some(team)
+    }
+    
+    //add prize values in basis points to the tournament
     pub(crate) fn internal_add_prizes_to_tournament(
         &mut self,
         tournament_id: &TournamentId,
-        percents_map: &HashMap<u8,u8>
+        basis_points_map: &HashMap<u32,u16>
         
     ) {
         //get the map of prizes for the given tournament
-        let mut prizes_map = self.winners_percents_per_tournament.get(tournament_id).unwrap_or_else(|| {
-            //if the tournament doesn't have any players, we create a new map 
-            LookupMap::new(
+        let mut prizes_map = self.winners_basis_points_per_tournament.get(tournament_id).unwrap_or_else(|| {
+            //if the tournament doesn't have any prizes, we create a new map 
+            UnorderedMap::new(
                 StorageKey::PrizesPerTournamentInner {
-                    //we get a new unique prefix for the collection
-                    tournament_id_hash: hash_tournament_id(&tournament_id, &"m".to_string()),
+                    //we get a new unique prefix for the collection, "m" was the prefix of the percents
+                    tournament_id_hash: hash_tournament_id(tournament_id, "b"),
                 }
                 .try_to_vec()
                 .unwrap(),
             )
         });
 
-        for (key, value) in percents_map {
-            prizes_map.insert(&key, &value);
+        for (key, value) in basis_points_map {
+            prizes_map.insert(key, value);
         }        
 
         //we insert that map for the given tournament ID. 
-        self.winners_percents_per_tournament.insert(tournament_id, &prizes_map);
+        self.winners_basis_points_per_tournament.insert(tournament_id, &prizes_map);
     }    
     
+    //replace prize values in basis points of the tournament
+    pub(crate) fn internal_replace_prizes_of_tournament(
+        &mut self,
+        tournament_id: &TournamentId,
+        basis_points_map: &HashMap<u32,u16>
+    ) {
+        if let Some(mut prizes_map) = self.winners_basis_points_per_tournament.get(tournament_id) {
+            //remove the old places, only the places with prizes are stored
+            prizes_map.clear();
+
+            //the map keeps its length in the value of the tournament
+            self.winners_basis_points_per_tournament.insert(tournament_id, &prizes_map);
+        }
+        
+        self.internal_add_prizes_to_tournament(tournament_id, basis_points_map);
+    }
+    
     //add a player to the set of players the tournament has
     pub(crate) fn internal_add_player_to_tournament(
         &mut self,
@@ -65,7 +597,7 @@ impl TournamentContract {
             UnorderedSet::new(
                 StorageKey::PlayersPerTournamentInner {
                     //we get a new unique prefix for the collection
-                    tournament_id_hash: hash_tournament_id(&tournament_id, &"s".to_string()),
+                    tournament_id_hash: hash_tournament_id(tournament_id, "s"),
                 }
                 .try_to_vec()
                 .unwrap(),
@@ -76,21 +608,120 @@ impl TournamentContract {
         let new_one = players_set.insert(player);
 
         //we insert that set for the given tournament ID. 
-        self.players_per_tournament.insert(&tournament_id, &players_set);
+        self.players_per_tournament.insert(tournament_id, &players_set);
         
         new_one
     }
     
+    //remove a player from the set of players the tournament has
+    pub(crate) fn internal_remove_player_from_tournament(
+        &mut self,
+        tournament_id: &TournamentId,
+        player: &AccountId,
+    ) -> bool {
+        if let Some(mut players_set) = self.players_per_tournament.get(tournament_id) {
+            let removed = players_set.remove(player);
+            
+            self.players_per_tournament.insert(tournament_id, &players_set);
+            
+            removed
+        } else {
+            false
+        }
+    }
+    
     //get number of players already in the tournament
     pub(crate) fn internal_get_players_number_in_tournament(
         &self,
         tournament_id: &TournamentId,     
-    ) -> u8 {    
+    ) -> u32 {    
         //get the set of tokens for the given account
         if let Some (players_set) = self.players_per_tournament.get(tournament_id) {
-            players_set.len() as u8
+            u32::try_from(players_set.len()).expect("Players number is out of range")
         } else {
             0
         }   
     }
-} 
+    
+    //add a tournament to the set of tournaments the owner has
+    pub(crate) fn internal_add_tournament_to_owner(
+        &mut self,
+        owner_id: &AccountId,
+        tournament_id: &TournamentId,
+    ) {
+        //get the set of tournaments for the given owner
+        let mut tournaments_set = self.tournaments_per_owner.get(owner_id).unwrap_or_else(|| {
+            //if the owner doesn't have any tournaments, we create a new unordered set
+            UnorderedSet::new(
+                StorageKey::TournamentsPerOwnerInner {
+                    //we get a new unique prefix for the collection
+                    account_id_hash: hash_account_id(owner_id),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        
+        tournaments_set.insert(tournament_id);
+        
+        self.tournaments_per_owner.insert(owner_id, &tournaments_set);
+    }
+    
+    //remove a tournament from the set of tournaments the owner has
+    pub(crate) fn internal_remove_tournament_from_owner(
+        &mut self,
+        owner_id: &AccountId,
+        tournament_id: &TournamentId,
+    ) {
+        let mut tournaments_set = self.tournaments_per_owner.get(owner_id).expect("Tournament should be owned by the owner");
+        
+        tournaments_set.remove(tournament_id);
+        
+        //if the set is now empty, we remove the owner from the tournaments_per_owner collection
+        if tournaments_set.is_empty() {
+            self.tournaments_per_owner.remove(owner_id);
+        } else {
+            self.tournaments_per_owner.insert(owner_id, &tournaments_set);
+        }
+    }
+    
+    //add a tournament to the set of tournaments in the given state
+    pub(crate) fn internal_add_tournament_to_state(
+        &mut self,
+        state: TournamentState,
+        tournament_id: &TournamentId,
+    ) {
+        let mut tournaments_set = self.tournaments_per_state.get(&state).unwrap_or_else(|| {
+            //if there are no tournaments in the state, we create a new unordered set
+            UnorderedSet::new(
+                StorageKey::TournamentsPerStateInner { state }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+        
+        tournaments_set.insert(tournament_id);
+        
+        self.tournaments_per_state.insert(&state, &tournaments_set);
+    }
+    
+    //move the tournament to a new state keeping the state index up to date
+    pub(crate) fn internal_set_tournament_state(
+        &mut self,
+        tournament_id: &TournamentId,
+        tournament: &mut Tournament,
+        state: TournamentState,
+    ) {
+        if let Some(mut tournaments_set) = self.tournaments_per_state.get(&tournament.state) {
+            tournaments_set.remove(tournament_id);
+            self.tournaments_per_state.insert(&tournament.state, &tournaments_set);
+        }
+        
+        *self.stats.state_counter(tournament.state) -= 1;
+        *self.stats.state_counter(state) += 1;
+        
+        tournament.state = state;
+        
+        self.internal_add_tournament_to_state(state, tournament_id);
+    }
+}
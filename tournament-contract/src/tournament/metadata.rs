@@ -1,10 +1,24 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::serde::{Deserialize, Serialize};
-use near_sdk::json_types::{U64, U128};
+use near_sdk::json_types::{U64, U128, Base64VecU8};
 use near_sdk::{AccountId};
+use std::collections::HashMap;
 
 pub type TournamentId = String;
 
+//limits of the tournament metadata to keep the storage cost per tournament bounded
+pub const MAX_NAME_LENGTH: usize = 128;
+pub const MAX_DESCRIPTION_LENGTH: usize = 2048;
+pub const MAX_GAME_LENGTH: usize = 64;
+pub const MAX_URL_LENGTH: usize = 512;
+pub const MAX_LINKS_NUMBER: usize = 8;
+pub const MAX_EXTRA_LENGTH: usize = 2048;
+pub const REFERENCE_HASH_LENGTH: usize = 32;
+pub const MAX_TEAM_NAME_LENGTH: usize = 64;
+pub const INVITE_CODE_HASH_LENGTH: usize = 32;
+pub const MAX_GATES_NUMBER: usize = 4;
+pub const MAX_PAYOUT_TIERS_NUMBER: usize = 8;
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TournamentContractMetadata {
@@ -17,17 +31,342 @@ pub struct TournamentContractMetadata {
 pub struct TournamentMetadata {
     pub name: String,                
     pub icon: Option<String>,
-    pub players_number: u8,
+    pub description: Option<String>,
+    //game title or category of the tournament
+    pub game: Option<String>,
+    //URL to the rules document
+    pub reference: Option<String>,
+    //base64-encoded sha256 hash of the rules document
+    pub reference_hash: Option<Base64VecU8>,
+    //external links (stream, discord, bracket...)
+    pub links: Option<Vec<String>>,
+    //free-form JSON string
+    pub extra: Option<String>,
+    //registration closes when the tournament starts (nanoseconds since epoch)
+    pub starts_at: Option<U64>,
+    pub ends_at: Option<U64>,
+    pub players_number: u32,
     pub in_price: u128,
+    //members of each team in the team tournaments, the players number counts the teams
+    pub team_size: Option<u8>,
+    //who can enter the tournament
+    #[serde(default)]
+    pub access: TournamentAccess,
+    //the players have to pass one of the gates to enter
+    pub gates: Option<Vec<EntryGate>>,
+}
+
+//Access mode of the tournament
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum TournamentAccess {
+    //any account with enough NEAR
+    #[default]
+    Public,
+    //the accounts the owner allowed
+    Allowlist,
+    //the accounts with an invite code the owner added the hash of
+    InviteCode,
+}
+
+//Entry rule of the gated tournament checked with a view call of another contract
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum EntryGate {
+    //the player holds a token of the NFT collection (`nft_tokens_for_owner`)
+    Nft { contract_id: AccountId },
+    //the player holds at least `min_balance` of the fungible token (`ft_balance_of`)
+    Ft { contract_id: AccountId, min_balance: U128 },
+}
+
+//The prizes of the tournament when the number of its entrants is in the range
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PayoutTier {
+    pub min_entrants: u32,
+    pub max_entrants: u32,
+    //the prize of each place in basis points, 10000 is the whole prize fond
+    pub basis_points_map: HashMap<u32,u16>,
+}
+
+//checks the URL has a known scheme and fits the length limit
+fn check_url(field: &str, url: &str) -> Result<(), String> {
+    ensure!(url.len() <= MAX_URL_LENGTH, "{} is longer than {} bytes", field, MAX_URL_LENGTH);
+    ensure!(
+        ["https://", "http://", "ipfs://", "ar://"].iter().any(|scheme| url.starts_with(scheme)),
+        "{} should be an http(s), ipfs or ar URL", field
+    );
+    Ok(())
+}
+
+impl TournamentMetadata {
+    //checks the lengths and formats of the metadata fields
+    pub fn validate(&self) -> Result<(), String> {
+        ensure!(!self.name.is_empty(), "Name is empty");
+        ensure!(self.name.len() <= MAX_NAME_LENGTH, "Name is longer than {} bytes", MAX_NAME_LENGTH);
+        
+        if let Some(icon) = &self.icon {
+            //icons can be also inlined as data URLs
+            if !icon.starts_with("data:image/") {
+                check_url("Icon", icon)?;
+            }
+            ensure!(icon.len() <= MAX_URL_LENGTH, "Icon is longer than {} bytes", MAX_URL_LENGTH);
+        }
+        
+        if let Some(description) = &self.description {
+            ensure!(description.len() <= MAX_DESCRIPTION_LENGTH, "Description is longer than {} bytes", MAX_DESCRIPTION_LENGTH);
+        }
+        
+        if let Some(game) = &self.game {
+            ensure!(game.len() <= MAX_GAME_LENGTH, "Game is longer than {} bytes", MAX_GAME_LENGTH);
+        }
+        
+        //the reference and its hash go together
+        ensure!(self.reference.is_some() == self.reference_hash.is_some(), "Reference and reference hash should be provided together");
+        if let Some(reference) = &self.reference {
+            check_url("Reference", reference)?;
+        }
+        if let Some(reference_hash) = &self.reference_hash {
+            ensure!(reference_hash.0.len() == REFERENCE_HASH_LENGTH, "Reference hash should be {} bytes", REFERENCE_HASH_LENGTH);
+        }
+        
+        if let Some(links) = &self.links {
+            ensure!(links.len() <= MAX_LINKS_NUMBER, "No more than {} links are allowed", MAX_LINKS_NUMBER);
+            for link in links {
+                check_url("Link", link)?;
+            }
+        }
+        
+        if let Some(extra) = &self.extra {
+            ensure!(extra.len() <= MAX_EXTRA_LENGTH, "Extra is longer than {} bytes", MAX_EXTRA_LENGTH);
+            ensure!(serde_json::from_str::<serde_json::Value>(extra).is_ok(), "Extra should be a JSON string");
+        }
+        
+        if let Some(team_size) = self.team_size {
+            ensure!(team_size > 0, "Team size should be more than zero");
+        }
+        
+        if let Some(gates) = &self.gates {
+            ensure!(gates.len() <= MAX_GATES_NUMBER, "No more than {} entry gates are allowed", MAX_GATES_NUMBER);
+            ensure!(self.team_size.is_none(), "Entry gates are for solo tournaments only");
+        }
+        
+        Ok(())
+    }
+    
+    //panics with the message of the first invalid field
+    pub fn assert_valid(&self) {
+        if let Err(message) = self.validate() {
+            panic!("{}", message);
+        }
+    }
+}
+
+//Changes of the tournament metadata requested by the owner, omitted fields are kept as they are.
+//Only the cosmetic fields (`name` to `extra`) can be changed once players have joined,
+//an empty string or list clears the optional field.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentMetadataUpdate {
+    pub name: Option<String>,
+    pub icon: Option<String>,
+    pub description: Option<String>,
+    pub game: Option<String>,
+    //the reference and its hash are updated together
+    pub reference: Option<String>,
+    pub reference_hash: Option<Base64VecU8>,
+    pub links: Option<Vec<String>>,
+    pub extra: Option<String>,
+    pub starts_at: Option<U64>,
+    pub ends_at: Option<U64>,
+    pub players_number: Option<u32>,
+    pub in_price: Option<U128>,
+    pub basis_points_map: Option<HashMap<u32,u16>>,
+    //zero turns the team tournament into the solo one
+    pub team_size: Option<u8>,
+    pub access: Option<TournamentAccess>,
+    //an empty list removes the gates
+    pub gates: Option<Vec<EntryGate>>,
+}
+
+//Arguments of `tournament_create`, the optional metadata is set with the builder methods
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentCreateArgs {
+    pub tournament_id: TournamentId,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_hash: Option<Base64VecU8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extra: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starts_at: Option<U64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<U64>,
+    pub players_number: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_size: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access: Option<TournamentAccess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gates: Option<Vec<EntryGate>>,
+    pub in_price: U128,
+    pub tournament_owner_id: AccountId,
+    //prize of each place in basis points, 10000 is the whole prize fond
+    pub basis_points_map: HashMap<u32,u16>,
+}
+
+impl TournamentCreateArgs {
+    pub fn new(
+        tournament_id: TournamentId,
+        name: String,
+        players_number: u32,
+        in_price: u128,
+        tournament_owner_id: AccountId,
+        basis_points_map: HashMap<u32,u16>,
+    ) -> Self {
+        Self {
+            tournament_id,
+            name,
+            icon: None,
+            description: None,
+            game: None,
+            reference: None,
+            reference_hash: None,
+            links: None,
+            extra: None,
+            starts_at: None,
+            ends_at: None,
+            players_number,
+            team_size: None,
+            access: None,
+            gates: None,
+            in_price: U128(in_price),
+            tournament_owner_id,
+            basis_points_map,
+        }
+    }
+
+    pub fn icon(mut self, icon: String) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn description(mut self, description: String) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn game(mut self, game: String) -> Self {
+        self.game = Some(game);
+        self
+    }
+
+    //The off-chain metadata and the sha256 hash of its content
+    pub fn reference(mut self, reference: String, reference_hash: Vec<u8>) -> Self {
+        self.reference = Some(reference);
+        self.reference_hash = Some(reference_hash.into());
+        self
+    }
+
+    pub fn links(mut self, links: Vec<String>) -> Self {
+        self.links = Some(links);
+        self
+    }
+
+    //Any JSON data of the game
+    pub fn extra(mut self, extra: String) -> Self {
+        self.extra = Some(extra);
+        self
+    }
+
+    //Teams of up to `team_size` members take the seats, `players_number` counts the teams
+    pub fn team_size(mut self, team_size: u8) -> Self {
+        self.team_size = Some(team_size);
+        self
+    }
+
+    //Only the allowlisted accounts or the holders of an invite code can enter
+    pub fn access(mut self, access: TournamentAccess) -> Self {
+        self.access = Some(access);
+        self
+    }
+
+    //Only the holders of a token of one of the gates can enter
+    pub fn gates(mut self, gates: Vec<EntryGate>) -> Self {
+        self.gates = Some(gates);
+        self
+    }
+
+    //Registration closes at `starts_at`, both are nanoseconds since epoch
+    pub fn schedule(mut self, starts_at: Option<u64>, ends_at: Option<u64>) -> Self {
+        self.starts_at = starts_at.map(U64);
+        self.ends_at = ends_at.map(U64);
+        self
+    }
+}
+
+//Lifecycle state of the tournament
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+#[serde(rename_all = "snake_case")]
+pub enum TournamentState {
+    //players can join the tournament and the owner can reward the prizes
+    Open,
+    //the prizes are rewarded
+    Completed,
+    //the tournament is cancelled by the owner and the players are refunded
+    Cancelled,
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct Tournament {
     pub owner_id: AccountId,
-    pub active: bool,
+    //the account the owner proposed to hand the tournament over to
+    pub pending_owner_id: Option<AccountId>,
+    pub state: TournamentState,
     pub balance: u128,
 }
 
+//The team registered by its captain, the captain's seat in the tournament is the team's seat
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Team {
+    pub name: String,
+    pub members: Vec<AccountId>,
+    //prize shares of the members in the order of the members
+    pub shares: Vec<u16>,
+}
+
+//The Json team is what will be returned from the team view call.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTeam {
+    pub captain_id: AccountId,
+    pub name: String,
+    pub members: Vec<AccountId>,
+    pub shares: Vec<u16>,
+}
+
+//The sponsor of the prize pool with the sum of its contributions
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonSponsor {
+    pub account_id: AccountId,
+    pub amount: U128,
+}
+
 //The Json tournament is what will be returned from view calls. 
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -36,9 +375,12 @@ pub struct JsonTournament {
     pub tournament_id: TournamentId,
     //owner of the tournament
     pub owner_id: AccountId,
+    //account the ownership was proposed to, if any
+    pub pending_owner_id: Option<AccountId>,
     //tournament metadata
     pub metadata: TournamentMetadata,
     
+    //prizes of the first places in basis points
     pub first_place_prize: U64,
     
     pub second_place_prize: U64,
@@ -48,6 +390,101 @@ pub struct JsonTournament {
     //is the tournament active
     pub active: bool,
     
+    //lifecycle state of the tournament
+    pub state: TournamentState,
+    
     //total prize fond for the tournament
     pub prize_fond: U128,    
+    
+    //accounts that added to the prize fond with `sponsor_tournament`
+    #[serde(default)]
+    pub sponsors: Vec<JsonSponsor>,
+    
+    //prize fond the owner guaranteed when creating the tournament, if any
+    #[serde(default)]
+    pub guaranteed_prize_fond: Option<U128>,
+    
+    //prize fond the winners share, the guarantee covers the gap while the tournament is open
+    pub effective_prize_fond: U128,
+    
+    //prizes by the number of entrants, the places prizes above are of the active tier
+    #[serde(default)]
+    pub payout_tiers: Vec<PayoutTier>,
+    
+    //index of the payout tier matching the entrants, none when the base prizes are used.
+    //The entrants can't change once the registration closes at `starts_at`, so neither can the tier.
+    //It is stored at the payout or the cancel, which close the registration without `starts_at` too
+    #[serde(default)]
+    pub active_payout_tier: Option<u8>,
+}
+
+//Aggregate counters of the contract updated at each mutation
+#[derive(BorshDeserialize, BorshSerialize, Default)]
+pub struct TournamentStats {
+    pub tournaments_total: u64,
+    pub tournaments_open: u64,
+    pub tournaments_completed: u64,
+    pub tournaments_cancelled: u64,
+    //total players registered in all the tournaments
+    pub players_total: u64,
+    //NEAR locked in the prize pools
+    pub locked_balance: u128,
+    //NEAR paid out to the winners
+    pub paid_out: u128,
+}
+
+impl TournamentStats {
+    //get the counter of the tournaments in the given state
+    pub(crate) fn state_counter(&mut self, state: TournamentState) -> &mut u64 {
+        match state {
+            TournamentState::Open => &mut self.tournaments_open,
+            TournamentState::Completed => &mut self.tournaments_completed,
+            TournamentState::Cancelled => &mut self.tournaments_cancelled,
+        }
+    }
+}
+
+//The Json stats is what will be returned from the stats view call.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonTournamentStats {
+    pub tournaments_total: U64,
+    pub tournaments_open: U64,
+    pub tournaments_completed: U64,
+    pub tournaments_cancelled: U64,
+    pub players_total: U64,
+    pub locked_balance: U128,
+    pub paid_out: U128,
+}
+
+//The prize of a winner returned from the rewards preview
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonPrize {
+    pub place: u32,
+    pub account_id: AccountId,
+    pub amount: U128,
+}
+
+//The prizes `reward_prizes` would transfer, ordered by place
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonRewardsPreview {
+    pub winners: Vec<JsonPrize>,
+    pub rewarded_amount: U128,
+    //the rest of the prize fond the owner can withdraw
+    pub remainder: U128,
+}
+
+//Filter for the tournaments enumeration, all the given conditions have to match
+#[derive(Serialize, Deserialize, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TournamentFilter {
+    pub state: Option<TournamentState>,
+    pub owner_id: Option<AccountId>,
+    pub min_in_price: Option<U128>,
+    pub max_in_price: Option<U128>,
+    //only tournaments with (or without) free places
+    pub has_free_places: Option<bool>,
+    pub game: Option<String>,
 }
@@ -1,13 +1,29 @@
+#[macro_use]
+pub mod validation;
 pub mod tournament_core;
 mod metadata;
 mod internal;
 //mod create;
 pub mod enumeration;
+pub mod ownership;
+pub mod teams;
+pub mod access;
+pub mod gates;
+pub mod sponsors;
+pub mod tiers;
 pub mod events;
+#[cfg(test)]
+pub(crate) mod testing;
 
 pub use self::metadata::*;
 //pub use self::create::*;
 pub use self::tournament_core::TournamentContract;
 pub use self::tournament_core::TournamentContractCore;
 pub use self::enumeration::*;
+pub use self::ownership::*;
+pub use self::teams::*;
+pub use self::access::*;
+pub use self::gates::*;
+pub use self::sponsors::*;
+pub use self::tiers::*;
 
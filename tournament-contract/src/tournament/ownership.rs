@@ -0,0 +1,190 @@
+use crate::*;
+use crate::tournament::events::{TournamentOwnerProposeLog, TournamentOwnerAcceptLog, TournamentOwnerCancelLog};
+
+pub trait TournamentContractOwnership {
+    //propose a new owner for the tournament (the proposed account has to accept it)
+    fn tournament_propose_owner(&mut self, tournament_id: TournamentId, new_owner_id: AccountId);
+
+    //accept the ownership of the tournament proposed by the current owner
+    fn tournament_accept_owner(&mut self, tournament_id: TournamentId);
+
+    //withdraw the pending ownership proposal of the tournament
+    fn tournament_cancel_owner(&mut self, tournament_id: TournamentId);
+}
+
+impl TournamentContractOwnership for TournamentContract {
+    //propose a new owner for the tournament (the proposed account has to accept it)
+    fn tournament_propose_owner(&mut self, tournament_id: TournamentId, new_owner_id: AccountId) {
+        let mut tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+
+        //check the owner calls this method
+        assert_eq!(env::predecessor_account_id(), tournament.owner_id, "Owner's method");
+
+        //the tournament can't be handed over to the current owner
+        assert_ne!(new_owner_id, tournament.owner_id, "Already the owner of the tournament");
+
+        //save the proposal, a new proposal replaces the previous one
+        tournament.pending_owner_id = Some(new_owner_id.clone());
+        self.tournaments_by_id.insert(&tournament_id, &tournament);
+
+        TournamentOwnerProposeLog{
+            tournament_id: &tournament_id,
+            old_owner_id: &tournament.owner_id,
+            new_owner_id: &new_owner_id,
+        }.emit();
+    }
+
+    //accept the ownership of the tournament proposed by the current owner
+    fn tournament_accept_owner(&mut self, tournament_id: TournamentId) {
+        let mut tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+
+        let account_id = env::predecessor_account_id();
+
+        //check the proposed owner calls this method
+        assert_eq!(tournament.pending_owner_id.as_ref(), Some(&account_id), "Ownership wasn't proposed to the caller");
+
+        //hand the tournament over to the new owner
+        let old_owner_id = std::mem::replace(&mut tournament.owner_id, account_id);
+        tournament.pending_owner_id = None;
+        self.tournaments_by_id.insert(&tournament_id, &tournament);
+
+        //move the tournament to the new owner's index
+        self.internal_remove_tournament_from_owner(&old_owner_id, &tournament_id);
+        self.internal_add_tournament_to_owner(&tournament.owner_id, &tournament_id);
+
+        TournamentOwnerAcceptLog{
+            tournament_id: &tournament_id,
+            old_owner_id: &old_owner_id,
+            new_owner_id: &tournament.owner_id,
+        }.emit();
+    }
+
+    //withdraw the pending ownership proposal of the tournament
+    fn tournament_cancel_owner(&mut self, tournament_id: TournamentId) {
+        let mut tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+
+        //check the owner calls this method
+        assert_eq!(env::predecessor_account_id(), tournament.owner_id, "Owner's method");
+
+        let pending_owner_id = tournament.pending_owner_id.take().expect("No ownership proposal");
+        self.tournaments_by_id.insert(&tournament_id, &tournament);
+
+        TournamentOwnerCancelLog{
+            tournament_id: &tournament_id,
+            old_owner_id: &tournament.owner_id,
+            new_owner_id: &pending_owner_id,
+        }.emit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_parser::*;
+    use crate::tournament::testing::*;
+    use near_sdk::test_utils::accounts;
+
+    const TOURNAMENT_ID: &str = "tournament-1";
+
+    //a tournament of accounts(0)
+    fn setup() -> TournamentContract {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 8, IN_PRICE);
+        contract
+    }
+
+    fn owner_tournaments(contract: &TournamentContract, account_id: AccountId) -> Vec<TournamentId> {
+        contract.tournaments_for_owner(account_id, None, None).into_iter()
+            .map(|tournament| tournament.tournament_id)
+            .collect()
+    }
+
+    #[test]
+    fn propose_and_accept_owner() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.tournament_propose_owner(TOURNAMENT_ID.to_string(), accounts(1));
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentOwnerPropose(vec![TournamentOwnerChangeData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            old_owner_id: accounts(0),
+            new_owner_id: accounts(1),
+        }])]);
+        //the owner doesn't change until the proposal is accepted
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().owner_id, accounts(0));
+
+        set_context(accounts(1), 0);
+        contract.tournament_accept_owner(TOURNAMENT_ID.to_string());
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentOwnerAccept(vec![TournamentOwnerChangeData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            old_owner_id: accounts(0),
+            new_owner_id: accounts(1),
+        }])]);
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.owner_id, accounts(1));
+        assert_eq!(tournament.pending_owner_id, None);
+
+        //the tournament moves to the index of the new owner
+        assert!(owner_tournaments(&contract, accounts(0)).is_empty());
+        assert_eq!(owner_tournaments(&contract, accounts(1)), vec![TOURNAMENT_ID.to_string()]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner's method")]
+    fn propose_owner_by_non_owner() {
+        let mut contract = setup();
+
+        set_context(accounts(1), 0);
+        contract.tournament_propose_owner(TOURNAMENT_ID.to_string(), accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Already the owner of the tournament")]
+    fn propose_owner_to_owner() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.tournament_propose_owner(TOURNAMENT_ID.to_string(), accounts(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "Ownership wasn't proposed to the caller")]
+    fn accept_owner_by_wrong_account() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.tournament_propose_owner(TOURNAMENT_ID.to_string(), accounts(1));
+
+        set_context(accounts(2), 0);
+        contract.tournament_accept_owner(TOURNAMENT_ID.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Ownership wasn't proposed to the caller")]
+    fn accept_cancelled_owner() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.tournament_propose_owner(TOURNAMENT_ID.to_string(), accounts(1));
+        contract.tournament_cancel_owner(TOURNAMENT_ID.to_string());
+        assert_eq!(logged_events().pop(), Some(TournamentEventData::TournamentOwnerCancel(vec![TournamentOwnerChangeData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            old_owner_id: accounts(0),
+            new_owner_id: accounts(1),
+        }])));
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().pending_owner_id, None);
+
+        set_context(accounts(1), 0);
+        contract.tournament_accept_owner(TOURNAMENT_ID.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "No ownership proposal")]
+    fn cancel_without_proposal() {
+        let mut contract = setup();
+
+        set_context(accounts(0), 0);
+        contract.tournament_cancel_owner(TOURNAMENT_ID.to_string());
+    }
+}
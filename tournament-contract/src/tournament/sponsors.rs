@@ -0,0 +1,154 @@
+use crate::*;
+use crate::tournament::events::TournamentSponsorLog;
+
+pub trait TournamentContractSponsors {
+    //add the attached deposit to the prize fond of the open tournament,
+    //the contributions are refunded to the sponsors if the tournament is cancelled
+    fn sponsor_tournament(&mut self, tournament_id: TournamentId);
+}
+
+impl TournamentContractSponsors for TournamentContract {
+    //add the attached deposit to the prize fond of the open tournament,
+    //the contributions are refunded to the sponsors if the tournament is cancelled
+    //#[payable]
+    fn sponsor_tournament(&mut self, tournament_id: TournamentId) {
+        let sponsor_id = env::predecessor_account_id();
+        let amount = env::attached_deposit();
+
+        let mut tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+
+        //check the tournament is active
+        assert_eq!(tournament.state, TournamentState::Open, "Tournament is inactive");
+
+        //the prize fond is frozen while paused
+        self.internal_assert_not_paused(&tournament_id);
+
+        assert!(amount > 0, "Attach the sponsored amount");
+
+        self.internal_add_sponsorship(&tournament_id, &sponsor_id, amount);
+
+        //save the prize fond balanse of the tournament
+        tournament.balance += amount;
+        self.tournaments_by_id.insert(&tournament_id, &tournament);
+
+        self.stats.locked_balance += amount;
+
+        TournamentSponsorLog{
+            tournament_id: &tournament_id,
+            sponsor_id: &sponsor_id,
+            amount: &amount.into(),
+        }.emit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_parser::*;
+    use crate::tournament::testing::*;
+    use near_sdk::test_utils::accounts;
+
+    const TOURNAMENT_ID: &str = "tournament-1";
+
+    //a free tournament of accounts(0) for two players
+    fn setup() -> TournamentContract {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 2, 0);
+        contract
+    }
+
+    fn sponsor(contract: &mut TournamentContract, sponsor_id: AccountId, amount: u128) {
+        set_context(sponsor_id, amount);
+        contract.sponsor_tournament(TOURNAMENT_ID.to_string());
+    }
+
+    #[test]
+    fn sponsors_fund_free_tournament() {
+        let mut contract = setup();
+        sponsor(&mut contract, accounts(3), 500);
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentSponsor(vec![TournamentSponsorData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            sponsor_id: accounts(3),
+            amount: U128(500),
+        }])]);
+
+        sponsor(&mut contract, accounts(4), 300);
+        sponsor(&mut contract, accounts(3), 200);
+
+        //the players enter for free
+        set_context(accounts(1), 0);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.prize_fond, U128(1_000));
+        assert_eq!(tournament.sponsors, vec![
+            JsonSponsor { account_id: accounts(3), amount: U128(700) },
+            JsonSponsor { account_id: accounts(4), amount: U128(300) },
+        ]);
+        assert_eq!(contract.stats.locked_balance, 1_000);
+
+        //the prizes are paid out of the sponsored prize fond
+        let mut winners_map = HashMap::new();
+        winners_map.insert(1, accounts(1));
+        assert_eq!(contract.preview_rewards(TOURNAMENT_ID.to_string(), winners_map).rewarded_amount, U128(600));
+    }
+
+    #[test]
+    fn cancel_refunds_sponsors() {
+        let mut contract = setup();
+        sponsor(&mut contract, accounts(3), 500);
+        set_context(accounts(1), 0);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+
+        set_context(accounts(0), 0);
+        contract.cancel_tournament(TOURNAMENT_ID.to_string());
+
+        //the free seat has nothing to refund
+        assert_eq!(logged_events(), vec![
+            TournamentEventData::TournamentCancel(vec![TournamentCancelData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                refunded_amount: U128(500),
+            }]),
+            TournamentEventData::TournamentRefund(vec![TournamentRefundData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                account_id: accounts(3),
+                amount: U128(500),
+            }]),
+        ]);
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(0));
+        assert_eq!(contract.stats.locked_balance, 0);
+    }
+
+    #[test]
+    fn leave_free_tournament() {
+        let mut contract = setup();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+        set_context(accounts(1), 0);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+
+        set_context(accounts(1), 0);
+        contract.leave_tournament(TOURNAMENT_ID.to_string());
+
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentLeave(vec![TournamentLeaveData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            player_id: accounts(1),
+        }])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Attach the sponsored amount")]
+    fn sponsor_without_deposit() {
+        let mut contract = setup();
+        sponsor(&mut contract, accounts(3), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament is inactive")]
+    fn sponsor_cancelled_tournament() {
+        let mut contract = setup();
+        set_context(accounts(0), 0);
+        contract.cancel_tournament(TOURNAMENT_ID.to_string());
+
+        sponsor(&mut contract, accounts(3), 500);
+    }
+}
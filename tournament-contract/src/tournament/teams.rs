@@ -0,0 +1,315 @@
+use crate::*;
+use crate::tournament::events::{TournamentTeamRegisterLog, TournamentTeamUpdateLog};
+use crate::tournament::validation::check_team;
+
+pub trait TournamentContractTeams {
+    //register the team of the caller (its captain) paying one entrance for the whole team,
+    //invite code tournaments take the code of the captain
+    fn register_team(&mut self, tournament_id: TournamentId, name: String, members: Vec<AccountId>, shares: Option<Vec<u16>>, invite_code: Option<String>);
+
+    //change the name, members or prize shares of the caller's team before the registration closes
+    fn update_team(&mut self, tournament_id: TournamentId, name: Option<String>, members: Option<Vec<AccountId>>, shares: Option<Vec<u16>>);
+
+    //get the team of the captain
+    fn display_team(&self, tournament_id: TournamentId, captain_id: AccountId) -> Option<JsonTeam>;
+}
+
+//checks the team against the team size of the tournament
+fn assert_valid_team(team: &Team, team_size: u8) {
+    if let Err(message) = check_team(&team.name, &team.members, &team.shares, team_size) {
+        panic!("{}", message);
+    }
+}
+
+impl TournamentContract {
+    //every member of the team has to be on the allowlist of the allowlist tournaments
+    fn internal_assert_members_allowed(&self, tournament_id: &TournamentId, metadata: &TournamentMetadata, team: &Team) {
+        if metadata.access == TournamentAccess::Allowlist {
+            for member in team.members.iter() {
+                self.internal_assert_allowed(tournament_id, member);
+            }
+        }
+    }
+}
+
+impl TournamentContractTeams for TournamentContract {
+    //register the team of the caller (its captain) paying one entrance for the whole team,
+    //invite code tournaments take the code of the captain
+    //#[payable]
+    fn register_team(&mut self, tournament_id: TournamentId, name: String, members: Vec<AccountId>, shares: Option<Vec<u16>>, invite_code: Option<String>) {
+        let captain_id = env::predecessor_account_id();
+
+        let mut tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+        let metadata = self.tournament_metadata_by_id.get(&tournament_id).unwrap();
+
+        let team_size = metadata.team_size.expect("Solo tournament, participate as a player");
+
+        //the prize is split equally by default
+        let shares = shares.unwrap_or_else(|| vec![1; members.len()]);
+        let team = Team { name, members, shares };
+        assert_valid_team(&team, team_size);
+        self.internal_assert_members_allowed(&tournament_id, &metadata, &team);
+
+        //the captain takes the seat of the team
+        self.internal_enter_tournament(&tournament_id, &mut tournament, &metadata, &captain_id, &captain_id, invite_code);
+        self.internal_add_team(&tournament_id, &captain_id, &team);
+
+        TournamentTeamRegisterLog{
+            tournament_id: &tournament_id,
+            captain_id: &captain_id,
+            name: &team.name,
+            members: &team.members,
+            shares: &team.shares,
+        }.emit();
+    }
+
+    //change the name, members or prize shares of the caller's team before the registration closes
+    fn update_team(&mut self, tournament_id: TournamentId, name: Option<String>, members: Option<Vec<AccountId>>, shares: Option<Vec<u16>>) {
+        let captain_id = env::predecessor_account_id();
+
+        let tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+
+        //check the tournament is active
+        assert_eq!(tournament.state, TournamentState::Open, "Tournament is inactive");
+
+        //rosters are frozen while paused
+        self.internal_assert_not_paused(&tournament_id);
+
+        let metadata = self.tournament_metadata_by_id.get(&tournament_id).unwrap();
+
+        //check the registration is still open
+        if let Some(starts_at) = metadata.starts_at {
+            assert!(env::block_timestamp() < starts_at.0, "Registration is closed");
+        }
+
+        assert!(name.is_some() || members.is_some() || shares.is_some(), "Nothing to update");
+
+        let mut team = self.internal_remove_team(&tournament_id, &captain_id).expect("Not a captain in the tournament");
+
+        if let Some(name) = name {
+            team.name = name;
+        }
+        if let Some(members) = members {
+            //new members without new shares get equal shares
+            team.shares = vec![1; members.len()];
+            team.members = members;
+        }
+        if let Some(shares) = shares {
+            team.shares = shares;
+        }
+        assert_valid_team(&team, metadata.team_size.unwrap());
+        self.internal_assert_members_allowed(&tournament_id, &metadata, &team);
+
+        self.internal_add_team(&tournament_id, &captain_id, &team);
+
+        TournamentTeamUpdateLog{
+            tournament_id: &tournament_id,
+            captain_id: &captain_id,
+            name: &team.name,
+            members: &team.members,
+            shares: &team.shares,
+        }.emit();
+    }
+
+    //get the team of the captain
+    fn display_team(&self, tournament_id: TournamentId, captain_id: AccountId) -> Option<JsonTeam> {
+        let team = self.teams_per_tournament.get(&tournament_id)?.get(&captain_id)?;
+
+        Some(JsonTeam {
+            captain_id,
+            name: team.name,
+            members: team.members,
+            shares: team.shares,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_parser::*;
+    use crate::tournament::testing::*;
+    use near_sdk::test_utils::accounts;
+
+    const TOURNAMENT_ID: &str = "tournament-1";
+
+    //a tournament of accounts(0) for two teams of up to two members
+    fn setup() -> TournamentContract {
+        let mut contract = new_contract();
+        create_team_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 2, 2);
+        contract
+    }
+
+    fn account(account_id: &str) -> AccountId {
+        account_id.parse().unwrap()
+    }
+
+    //register the team of the captain with the exact in price
+    fn register(contract: &mut TournamentContract, captain_id: AccountId, members: Vec<AccountId>, shares: Option<Vec<u16>>) {
+        set_context(captain_id, IN_PRICE);
+        contract.register_team(TOURNAMENT_ID.to_string(), "Team".to_string(), members, shares, None);
+    }
+
+    #[test]
+    fn register_team_takes_one_seat() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), account("member.near")], Some(vec![3, 1]));
+
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(1)));
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(IN_PRICE));
+
+        let team = contract.display_team(TOURNAMENT_ID.to_string(), accounts(1)).unwrap();
+        assert_eq!(team.members, vec![accounts(1), account("member.near")]);
+        assert_eq!(team.shares, vec![3, 1]);
+
+        assert_eq!(logged_events(), vec![
+            TournamentEventData::TournamentEntrance(vec![TournamentEntranceData {
+                partisipator_id: accounts(1),
+                tournament_id: TOURNAMENT_ID.to_string(),
+                payer_id: None,
+            }]),
+            TournamentEventData::TournamentTeamRegister(vec![TournamentTeamData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                captain_id: accounts(1),
+                name: "Team".to_string(),
+                members: vec![accounts(1), account("member.near")],
+                shares: vec![3, 1],
+            }]),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Team tournament, register a team")]
+    fn participate_team_tournament() {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Team has more than 2 members")]
+    fn register_too_big_team() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2), accounts(3)], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Shares should be given for each member")]
+    fn register_team_without_shares() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], Some(vec![1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "is already in another team")]
+    fn register_member_of_another_team() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], None);
+        register(&mut contract, accounts(3), vec![accounts(3), accounts(2)], None);
+    }
+
+    #[test]
+    fn update_team_roster() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], None);
+
+        set_context(accounts(1), 0);
+        contract.update_team(TOURNAMENT_ID.to_string(), None, Some(vec![accounts(1), accounts(4)]), Some(vec![1, 2]));
+
+        let team = contract.display_team(TOURNAMENT_ID.to_string(), accounts(1)).unwrap();
+        assert_eq!(team.members, vec![accounts(1), accounts(4)]);
+        assert_eq!(team.shares, vec![1, 2]);
+
+        //the replaced member can join another team
+        register(&mut contract, accounts(3), vec![accounts(3), accounts(2)], None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Registration is closed")]
+    fn update_team_after_start() {
+        let mut contract = new_contract();
+        contract.tournament_create(
+            TournamentCreateArgs::new(TOURNAMENT_ID.to_string(), "Scheduled".to_string(), 2, IN_PRICE, accounts(0), basis_points_map())
+                .team_size(2)
+                .schedule(Some(1_000), Some(2_000)),
+        );
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], None);
+
+        set_context_at(accounts(1), 0, 1_000);
+        contract.update_team(TOURNAMENT_ID.to_string(), Some("Renamed".to_string()), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a captain in the tournament")]
+    fn update_team_by_member() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], None);
+
+        set_context(accounts(2), 0);
+        contract.update_team(TOURNAMENT_ID.to_string(), Some("Renamed".to_string()), None, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament is paused")]
+    fn update_team_of_paused_tournament() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], None);
+        contract.paused_tournaments.insert(&TOURNAMENT_ID.to_string());
+
+        set_context(accounts(1), 0);
+        contract.update_team(TOURNAMENT_ID.to_string(), Some("Renamed".to_string()), None, None);
+    }
+
+    #[test]
+    fn leave_tournament_removes_team() {
+        let mut contract = setup();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], None);
+
+        set_context(accounts(1), 0);
+        contract.leave_tournament(TOURNAMENT_ID.to_string());
+
+        assert!(contract.display_team(TOURNAMENT_ID.to_string(), accounts(1)).is_none());
+        //the former member is free to join another team
+        register(&mut contract, accounts(3), vec![accounts(3), accounts(2)], None);
+    }
+
+    #[test]
+    fn reward_prizes_split_between_members() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], Some(vec![3, 1]));
+        register(&mut contract, accounts(3), vec![accounts(3), accounts(4)], None);
+
+        let mut winners_map = HashMap::new();
+        winners_map.insert(1, accounts(1));
+        winners_map.insert(2, accounts(3));
+
+        let preview = contract.preview_rewards(TOURNAMENT_ID.to_string(), winners_map.clone());
+        assert_eq!(
+            preview.winners.iter().map(|prize| (prize.place, prize.account_id.clone(), prize.amount)).collect::<Vec<_>>(),
+            vec![
+                (1, accounts(1), U128(90)),
+                (1, accounts(2), U128(30)),
+                (2, accounts(3), U128(30)),
+                (2, accounts(4), U128(30)),
+            ],
+        );
+        assert_eq!(preview.rewarded_amount, U128(180));
+
+        set_context(accounts(0), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map);
+        assert_eq!(contract.stats.paid_out, 180);
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't a captain in the tournament")]
+    fn reward_prizes_to_member() {
+        let mut contract = setup();
+        register(&mut contract, accounts(1), vec![accounts(1), accounts(2)], None);
+
+        let mut winners_map = HashMap::new();
+        winners_map.insert(1, accounts(2));
+
+        set_context(accounts(0), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map);
+    }
+}
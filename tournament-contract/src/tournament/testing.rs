@@ -0,0 +1,110 @@
+//! Helpers shared by the unit tests of the tournament modules.
+
+use crate::*;
+use crate::event_parser::{parse_event_log, TournamentEventData};
+use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+use near_sdk::{testing_env, Balance, PromiseResult, RuntimeFeesConfig, VMConfig};
+
+/// In price of the tournaments created by `create_tournament`
+pub(crate) const IN_PRICE: u128 = 100;
+
+//set the caller and the attached deposit of the next call
+pub(crate) fn set_context(predecessor: AccountId, deposit: Balance) {
+    set_context_at(predecessor, deposit, 0);
+}
+
+//set the caller, the attached deposit and the block time of the next call
+pub(crate) fn set_context_at(predecessor: AccountId, deposit: Balance, block_timestamp: u64) {
+    let mut context = VMContextBuilder::new();
+    context
+        .current_account_id(accounts(0))
+        .predecessor_account_id(predecessor)
+        .attached_deposit(deposit)
+        .block_timestamp(block_timestamp);
+    testing_env!(context.build());
+}
+
+//set the caller of the callback and the results of the promises it waited for
+pub(crate) fn set_context_with_results(predecessor: AccountId, promise_results: Vec<PromiseResult>) {
+    let mut context = VMContextBuilder::new();
+    context
+        .current_account_id(accounts(0))
+        .predecessor_account_id(predecessor);
+    testing_env!(context.build(), VMConfig::default(), RuntimeFeesConfig::default(), HashMap::default(), promise_results);
+}
+
+pub(crate) fn new_contract() -> TournamentContract {
+    set_context(accounts(0), 0);
+    TournamentContract::new()
+}
+
+//the 60/30 prizes for the first and the second places
+pub(crate) fn basis_points_map() -> HashMap<u32,u16> {
+    let mut basis_points_map = HashMap::new();
+    basis_points_map.insert(1, 6_000);
+    basis_points_map.insert(2, 3_000);
+    basis_points_map
+}
+
+//create an open tournament of `owner_id` with the 60/30 prizes
+pub(crate) fn create_tournament(
+    contract: &mut TournamentContract,
+    tournament_id: &str,
+    owner_id: AccountId,
+    players_number: u32,
+    in_price: u128,
+) {
+    create_tournament_of(contract, tournament_id, owner_id, players_number, None, in_price);
+}
+
+//create an open tournament of `owner_id` for `players_number` teams with the 60/30 prizes
+pub(crate) fn create_team_tournament(
+    contract: &mut TournamentContract,
+    tournament_id: &str,
+    owner_id: AccountId,
+    players_number: u32,
+    team_size: u8,
+) {
+    create_tournament_of(contract, tournament_id, owner_id, players_number, Some(team_size), IN_PRICE);
+}
+
+fn create_tournament_of(
+    contract: &mut TournamentContract,
+    tournament_id: &str,
+    owner_id: AccountId,
+    players_number: u32,
+    team_size: Option<u8>,
+    in_price: u128,
+) {
+    let mut args = TournamentCreateArgs::new(
+        tournament_id.to_string(),
+        tournament_id.to_uppercase(),
+        players_number,
+        in_price,
+        owner_id,
+        basis_points_map(),
+    );
+    args.team_size = team_size;
+    contract.tournament_create(args);
+}
+
+//join the tournament with the exact in price
+pub(crate) fn join(contract: &mut TournamentContract, tournament_id: &str, player_id: AccountId) {
+    set_context(player_id, IN_PRICE);
+    contract.participate_tournament(tournament_id.to_string(), None);
+}
+
+//set the start time of the tournament, the players can leave it until then
+pub(crate) fn schedule_start(contract: &mut TournamentContract, tournament_id: &str, starts_at: u64) {
+    let mut metadata = contract.tournament_metadata_by_id.get(&tournament_id.to_string()).unwrap();
+    metadata.starts_at = Some(U64(starts_at));
+    contract.tournament_metadata_by_id.insert(&tournament_id.to_string(), &metadata);
+}
+
+//the events logged since the last `set_context`
+pub(crate) fn logged_events() -> Vec<TournamentEventData> {
+    get_logs()
+        .iter()
+        .map(|log| parse_event_log(log).expect("Not a tournament event").event)
+        .collect()
+}
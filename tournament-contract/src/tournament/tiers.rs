@@ -0,0 +1,258 @@
+use crate::*;
+use crate::tournament::events::TournamentUpdateLog;
+use crate::tournament::internal::assert_valid_payout_tiers;
+
+pub trait TournamentContractPayoutTiers {
+    //set the prizes by the number of entrants, the tier matching the entrants when the registration
+    //closes replaces the prizes of the tournament, an empty list removes the tiers
+    fn set_payout_tiers(&mut self, tournament_id: TournamentId, payout_tiers: Vec<PayoutTier>);
+}
+
+impl TournamentContractPayoutTiers for TournamentContract {
+    //set the prizes by the number of entrants, the tier matching the entrants when the registration
+    //closes replaces the prizes of the tournament, an empty list removes the tiers
+    fn set_payout_tiers(&mut self, tournament_id: TournamentId, mut payout_tiers: Vec<PayoutTier>) {
+        let tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+
+        //check the owner calls this method
+        assert_eq!(env::predecessor_account_id(), tournament.owner_id, "Owner's method");
+
+        //check the tournament is active
+        assert_eq!(tournament.state, TournamentState::Open, "Tournament is inactive");
+
+        //the prizes are locked once anyone has joined
+        assert!(self.internal_get_players_number_in_tournament(&tournament_id) == 0, "Payout tiers can't be changed after players have joined");
+
+        let metadata = self.tournament_metadata_by_id.get(&tournament_id).unwrap();
+
+        payout_tiers.sort_by_key(|tier| tier.min_entrants);
+        assert_valid_payout_tiers(&payout_tiers, metadata.players_number);
+
+        if payout_tiers.is_empty() {
+            self.payout_tiers_per_tournament.remove(&tournament_id);
+        } else {
+            self.payout_tiers_per_tournament.insert(&tournament_id, &payout_tiers);
+        }
+
+        TournamentUpdateLog{
+            tournament_id: &tournament_id,
+            updated_fields: &["payout_tiers"],
+        }.emit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_parser::*;
+    use crate::tournament::testing::*;
+    use near_sdk::test_utils::accounts;
+
+    const TOURNAMENT_ID: &str = "tournament-1";
+
+    fn tier(min_entrants: u32, max_entrants: u32, basis_points: &[(u32, u16)]) -> PayoutTier {
+        PayoutTier { min_entrants, max_entrants, basis_points_map: basis_points.iter().copied().collect() }
+    }
+
+    //"2-3 entrants pay the winner" and "4-6 entrants pay top 2" in a tournament of accounts(0) for 8 players
+    fn setup() -> TournamentContract {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(0), 8, IN_PRICE);
+        contract.set_payout_tiers(TOURNAMENT_ID.to_string(), vec![
+            tier(4, 6, &[(1, 7_000), (2, 3_000)]),
+            tier(2, 3, &[(1, 10_000)]),
+        ]);
+        contract
+    }
+
+    fn join_players(contract: &mut TournamentContract, players: &[AccountId]) {
+        for player_id in players {
+            join(contract, TOURNAMENT_ID, player_id.clone());
+        }
+    }
+
+    #[test]
+    fn tiers_are_sorted_and_logged() {
+        let contract = setup();
+        assert_eq!(logged_events().pop(), Some(TournamentEventData::TournamentUpdate(vec![TournamentUpdateData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            updated_fields: vec!["payout_tiers".to_string()],
+        }])));
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.payout_tiers, vec![tier(2, 3, &[(1, 10_000)]), tier(4, 6, &[(1, 7_000), (2, 3_000)])]);
+        //nobody entered, the base prizes are used
+        assert_eq!(tournament.active_payout_tier, None);
+        assert_eq!(tournament.first_place_prize, U64(6_000));
+    }
+
+    #[test]
+    fn tier_follows_entrants() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1), accounts(2)]);
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.active_payout_tier, Some(0));
+        assert_eq!(tournament.first_place_prize, U64(10_000));
+        assert_eq!(tournament.second_place_prize, U64(0));
+
+        join_players(&mut contract, &[accounts(3), accounts(4)]);
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.active_payout_tier, Some(1));
+        assert_eq!(tournament.first_place_prize, U64(7_000));
+        assert_eq!(tournament.second_place_prize, U64(3_000));
+    }
+
+    #[test]
+    fn reward_uses_active_tier() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1), accounts(2), accounts(3), accounts(4)]);
+
+        let mut winners_map = HashMap::new();
+        winners_map.insert(1, accounts(1));
+        winners_map.insert(2, accounts(2));
+
+        let preview = contract.preview_rewards(TOURNAMENT_ID.to_string(), winners_map.clone());
+        assert_eq!(preview.rewarded_amount, U128(4 * IN_PRICE));
+
+        set_context(accounts(0), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map);
+        assert_eq!(contract.stats.paid_out, 4 * IN_PRICE);
+    }
+
+    #[test]
+    fn tier_locked_at_payout() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1), accounts(2), accounts(3), accounts(4)]);
+
+        let mut winners_map = HashMap::new();
+        winners_map.insert(1, accounts(1));
+
+        set_context(accounts(0), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map);
+
+        assert_eq!(logged_events()[0], TournamentEventData::TournamentPayoutTierLock(vec![TournamentPayoutTierLockData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            entrants: 4,
+            tier: Some(1),
+        }]));
+        assert_eq!(contract.locked_payout_tiers_per_tournament.get(&TOURNAMENT_ID.to_string()), Some(Some(1)));
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.active_payout_tier, Some(1));
+        assert_eq!(tournament.first_place_prize, U64(7_000));
+    }
+
+    #[test]
+    fn tier_locked_at_cancel() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1), accounts(2)]);
+
+        set_context(accounts(0), 0);
+        contract.cancel_tournament(TOURNAMENT_ID.to_string());
+
+        assert_eq!(logged_events()[0], TournamentEventData::TournamentPayoutTierLock(vec![TournamentPayoutTierLockData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            entrants: 2,
+            tier: Some(0),
+        }]));
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().active_payout_tier, Some(0));
+    }
+
+    #[test]
+    fn locked_tier_stays_after_entrants_change() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1), accounts(2)]);
+        contract.internal_lock_payout_tier(&TOURNAMENT_ID.to_string());
+
+        join_players(&mut contract, &[accounts(3), accounts(4)]);
+
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().active_payout_tier, Some(0));
+    }
+
+    #[test]
+    fn base_prizes_locked_out_of_tiers() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1)]);
+
+        let mut winners_map = HashMap::new();
+        winners_map.insert(1, accounts(1));
+
+        set_context(accounts(0), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map);
+
+        assert_eq!(logged_events()[0], TournamentEventData::TournamentPayoutTierLock(vec![TournamentPayoutTierLockData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            entrants: 1,
+            tier: None,
+        }]));
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().active_payout_tier, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "No prize for the place 2")]
+    fn reward_place_without_prize_in_tier() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1), accounts(2)]);
+
+        let mut winners_map = HashMap::new();
+        winners_map.insert(1, accounts(1));
+        winners_map.insert(2, accounts(2));
+
+        set_context(accounts(0), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map);
+    }
+
+    #[test]
+    fn entrants_out_of_tiers_use_base_prizes() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1)]);
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.active_payout_tier, None);
+        assert_eq!(tournament.first_place_prize, U64(6_000));
+    }
+
+    #[test]
+    fn empty_tiers_remove_them() {
+        let mut contract = setup();
+        contract.set_payout_tiers(TOURNAMENT_ID.to_string(), vec![]);
+
+        assert!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().payout_tiers.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Entrants range 3-5 overlaps the previous one")]
+    fn overlapping_tiers() {
+        let mut contract = setup();
+        contract.set_payout_tiers(TOURNAMENT_ID.to_string(), vec![tier(2, 3, &[(1, 10_000)]), tier(3, 5, &[(1, 10_000)])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Place 3 is out of the players number")]
+    fn tier_places_above_min_entrants() {
+        let mut contract = setup();
+        contract.set_payout_tiers(TOURNAMENT_ID.to_string(), vec![tier(2, 8, &[(1, 5_000), (2, 3_000), (3, 2_000)])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Entrants range 4-6 is out of the players number")]
+    fn players_number_below_tiers() {
+        let mut contract = setup();
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            players_number: Some(5),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Payout tiers can't be changed after players have joined")]
+    fn set_tiers_after_entrance() {
+        let mut contract = setup();
+        join_players(&mut contract, &[accounts(1)]);
+
+        set_context(accounts(0), 0);
+        contract.set_payout_tiers(TOURNAMENT_ID.to_string(), vec![]);
+    }
+}
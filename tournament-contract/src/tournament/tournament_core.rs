@@ -1,103 +1,187 @@
-use near_sdk::{env, IntoStorageKey, AccountId, Balance, Promise};
+use near_sdk::{env, AccountId, Balance, CryptoHash, Promise};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{ LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::collections::{ LookupMap, LookupSet, UnorderedMap, UnorderedSet};
 use near_sdk::json_types::{ U64, U128};
 use std::collections::HashMap;
-use crate::tournament::events::{TournamentCreateLog, TournamentEntranceLog, TournamentPrizesRewardLog};
+use crate::tournament::events::{
+    TournamentCreateLog, TournamentPrizesRewardLog, TournamentWinnerLog, TournamentUpdateLog,
+    TournamentCancelLog, TournamentRefundLog, TournamentFeesWithdrawLog, TournamentGuaranteeLog,
+};
 
 use crate::tournament::metadata::{
-    TournamentId, Tournament, TournamentMetadata, JsonTournament
+    TournamentId, Tournament, TournamentState, TournamentMetadata, TournamentMetadataUpdate, JsonTournament,
+    TournamentStats, JsonPrize, JsonRewardsPreview, JsonSponsor, Team, PayoutTier, TournamentCreateArgs,
 };
 
-use crate::tournament::internal::{percent_calculation};
+use crate::StorageKey;
+use crate::tournament::internal::{assert_valid_basis_points, assert_valid_payout_tiers, assert_valid_schedule, non_empty};
 
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct TournamentContract {  
     //keeps track of all the players IDs for a given tournament
     pub players_per_tournament: LookupMap<TournamentId, UnorderedSet<AccountId>>,
     
-    //keeps winners refund distribution in percents for a given tournament
-    pub winners_percents_per_tournament: LookupMap<TournamentId, LookupMap<u8,u8>>,
+    //keeps winners refund distribution in basis points for a given tournament
+    pub winners_basis_points_per_tournament: LookupMap<TournamentId, UnorderedMap<u32,u16>>,
 
     //keeps track of the tournament struct for a given tournament ID
     pub tournaments_by_id: LookupMap<TournamentId, Tournament>,
 
     //keeps track of the tournament metadata for a given tournament ID
     pub tournament_metadata_by_id: UnorderedMap<TournamentId, TournamentMetadata>,    
+    
+    //keeps track of all the tournament IDs for a given owner
+    pub tournaments_per_owner: LookupMap<AccountId, UnorderedSet<TournamentId>>,
+    
+    //keeps track of all the tournament IDs in a given state
+    pub tournaments_per_state: LookupMap<TournamentState, UnorderedSet<TournamentId>>,
+    
+    //aggregate counters of all the tournaments
+    pub stats: TournamentStats,
+    
+    //entries and payouts are blocked in all the tournaments
+    pub paused: bool,
+    
+    //tournaments with the entries and payouts blocked
+    pub paused_tournaments: LookupSet<TournamentId>,
+    
+    //keeps the teams of a given tournament by their captains
+    pub teams_per_tournament: LookupMap<TournamentId, LookupMap<AccountId, Team>>,
+    
+    //keeps the captain of every team member of a given tournament
+    pub team_members_per_tournament: LookupMap<TournamentId, LookupMap<AccountId, AccountId>>,
+    
+    //keeps the accounts that paid for the players of a given tournament (only when it isn't the player)
+    pub payers_per_tournament: LookupMap<TournamentId, LookupMap<AccountId, AccountId>>,
+    
+    //keeps the accounts allowed to enter a given allowlist tournament
+    pub allowed_per_tournament: LookupMap<TournamentId, LookupSet<AccountId>>,
+    
+    //keeps the hashes of the invite codes of a given tournament, true for the single use ones
+    pub invite_codes_per_tournament: LookupMap<TournamentId, LookupMap<CryptoHash, bool>>,
+    
+    //keeps the contributions of the prize pool sponsors of a given tournament
+    pub sponsors_per_tournament: LookupMap<TournamentId, UnorderedMap<AccountId, Balance>>,
+    
+    //keeps the prize fond guaranteed by the owner of a given tournament
+    pub guarantees_per_tournament: LookupMap<TournamentId, Balance>,
+    
+    //keeps the payout tiers of a given tournament sorted by their entrants ranges
+    pub payout_tiers_per_tournament: LookupMap<TournamentId, Vec<PayoutTier>>,
+    
+    //keeps the payout tier locked at the payout or the cancel of a given tournament, None when the entrants matched no tier
+    pub locked_payout_tiers_per_tournament: LookupMap<TournamentId, Option<u8>>,
 }
 
 impl TournamentContract {
-    pub fn new<P,W,TI,TM>(        
-        players_per_tournament_prefix: P,       
-        winners_percents_per_tournament: W,
-        tournaments_by_id: TI,
-        tournament_metadata_by_id: TM,
-    ) -> Self
-        where 
-            P: IntoStorageKey,
-            W: IntoStorageKey,
-            TI: IntoStorageKey,
-            TM: IntoStorageKey,
-    {
-        let this = Self {
-            players_per_tournament: LookupMap::new(players_per_tournament_prefix),
-            winners_percents_per_tournament: LookupMap::new(winners_percents_per_tournament),
-            tournaments_by_id:LookupMap::new(tournaments_by_id),
-            tournament_metadata_by_id: UnorderedMap::new(tournament_metadata_by_id),
-        };
-        
-        this
-    }    
+    //the empty collections under the prefixes of the contract storage keys
+    pub(crate) fn new() -> Self {
+        Self {
+            players_per_tournament: LookupMap::new(StorageKey::PlayersPerTournament),
+            winners_basis_points_per_tournament: LookupMap::new(StorageKey::WinnersPercentPerTournament),
+            tournaments_by_id: LookupMap::new(StorageKey::TournamentsById),
+            tournament_metadata_by_id: UnorderedMap::new(StorageKey::TournamentMetadataById),
+            tournaments_per_owner: LookupMap::new(StorageKey::TournamentsPerOwner),
+            tournaments_per_state: LookupMap::new(StorageKey::TournamentsPerState),
+            stats: TournamentStats::default(),
+            paused: false,
+            paused_tournaments: LookupSet::new(StorageKey::PausedTournaments),
+            teams_per_tournament: LookupMap::new(StorageKey::TeamsPerTournament),
+            team_members_per_tournament: LookupMap::new(StorageKey::TeamMembersPerTournament),
+            payers_per_tournament: LookupMap::new(StorageKey::PayersPerTournament),
+            allowed_per_tournament: LookupMap::new(StorageKey::AllowedPerTournament),
+            invite_codes_per_tournament: LookupMap::new(StorageKey::InviteCodesPerTournament),
+            sponsors_per_tournament: LookupMap::new(StorageKey::SponsorsPerTournament),
+            guarantees_per_tournament: LookupMap::new(StorageKey::GuaranteesPerTournament),
+            payout_tiers_per_tournament: LookupMap::new(StorageKey::PayoutTiersPerTournament),
+            locked_payout_tiers_per_tournament: LookupMap::new(StorageKey::LockedPayoutTiersPerTournament),
+        }
+    }
 }
 
 pub trait TournamentContractCore {   
-    //tournament creation method
-    fn tournament_create(
-        &mut self,
-        tournament_id: TournamentId,
-        name: String,
-        icon: Option<String>,
-        players_number: u8,
-        in_price: U128,        
-        tournament_owner_id: AccountId,
-        percents_map: HashMap<u8,u8>,
-    );
+    //tournament creation method, the attached deposit is the prize fond guaranteed by the owner
+    fn tournament_create(&mut self, args: TournamentCreateArgs);
+
+    //change the tournament metadata, economic fields are locked once players have joined
+    fn tournament_update(&mut self, tournament_id: TournamentId, update: TournamentMetadataUpdate);
 
     //get the information for a specific tournament ID
     fn display_tournament(&self, tournament_id: TournamentId) -> Option<JsonTournament>;
     
-    //add player to the tournament with NEAR depositing
-    fn participate_tournament(&mut self, tournament_id: TournamentId);
+    //add player to the tournament with NEAR depositing, invite code tournaments take the code
+    fn participate_tournament(&mut self, tournament_id: TournamentId, invite_code: Option<String>);
+    
+    //add another player to the tournament paying the entrance, the refunds go back to the payer
+    fn participate_tournament_for(&mut self, tournament_id: TournamentId, player_id: AccountId, invite_code: Option<String>);
+    
+    //get the account that paid for the seat of the player
+    fn display_payer(&self, tournament_id: TournamentId, player_id: AccountId) -> Option<AccountId>;
     
     //get free places in the tournament
     fn display_freeplaces_in_tournament(&self, tournament_id: TournamentId) -> Option<U64>;
     
     //refunds the prizes for the winners 
-    fn reward_prizes(&mut self, tournament_id: TournamentId, winners_map: HashMap<u8,AccountId>);
+    fn reward_prizes(&mut self, tournament_id: TournamentId, winners_map: HashMap<u32,AccountId>);
+    
+    //get the prizes `reward_prizes` would transfer to the winners
+    fn preview_rewards(&self, tournament_id: TournamentId, winners_map: HashMap<u32,AccountId>) -> JsonRewardsPreview;
+    
+    //cancel the tournament refunding the players and the sponsors
+    fn cancel_tournament(&mut self, tournament_id: TournamentId);
+    
+    //leave the scheduled tournament before the registration closes getting the deposit back
+    fn leave_tournament(&mut self, tournament_id: TournamentId);
+    
+    //give up the seat paid for another player before the registration closes getting the deposit back
+    fn withdraw_sponsored_seat(&mut self, tournament_id: TournamentId, player_id: AccountId);
+    
+    //withdraw the rest of the prize fond of the completed tournament to the owner
+    fn withdraw_fees(&mut self, tournament_id: TournamentId);
 }
 
 
 
 impl TournamentContractCore for TournamentContract {
 
-    //tournament creation method
-    fn tournament_create(
-        &mut self,
-        tournament_id: TournamentId,
-        name: String,
-        icon: Option<String>,
-        players_number: u8,
-        in_price: U128,        
-        tournament_owner_id: AccountId,
-        percents_map: HashMap<u8,u8>,
-    ) {            
-        assert!(u128::from(in_price)>0,"Tournaments with zero in prise are not allowed");
+    //tournament creation method, the attached deposit is the prize fond guaranteed by the owner
+    fn tournament_create(&mut self, args: TournamentCreateArgs) {
+        let TournamentCreateArgs {
+            tournament_id,
+            name,
+            icon,
+            description,
+            game,
+            reference,
+            reference_hash,
+            links,
+            extra,
+            starts_at,
+            ends_at,
+            players_number,
+            team_size,
+            access,
+            gates,
+            in_price,
+            tournament_owner_id,
+            basis_points_map,
+        } = args;
+        assert_valid_basis_points(&basis_points_map, players_number);
+        
+        assert_valid_schedule(&starts_at, &ends_at);
+        
+        //the unused part of the guarantee goes back to the owner, so only the owner can guarantee
+        let guarantee = env::attached_deposit();
+        if guarantee > 0 {
+            assert_eq!(env::predecessor_account_id(), tournament_owner_id, "Only the owner can guarantee the prize fond");
+        }
         
         //specify the tornament struct that contains the owner ID 
         let tournament = Tournament {
             //set the owner ID equal to the tournament owner ID passed into the function
             owner_id: tournament_owner_id,
-            active: true,
+            pending_owner_id: None,
+            state: TournamentState::Open,
             balance: 0,            
         };
 
@@ -109,23 +193,182 @@ impl TournamentContractCore for TournamentContract {
         
         //specify the tournament metadata struct
         let metadata = TournamentMetadata {
-            name: name,                
-            icon: icon,
-            players_number: players_number,
+            name,                
+            icon,
+            description,
+            game,
+            reference,
+            reference_hash,
+            links,
+            extra,
+            starts_at,
+            ends_at,
+            players_number,
             in_price: u128::from(in_price),
+            team_size,
+            access: access.unwrap_or_default(),
+            gates: gates.filter(|gates| !gates.is_empty()),
         };
+        
+        metadata.assert_valid();
 
         //insert the tornament ID and metadata
         self.tournament_metadata_by_id.insert(&tournament_id, &metadata);
         
-        //insert the prizes percents for the tournament ID
-        self.internal_add_prizes_to_tournament(&tournament_id, &percents_map);
+        //insert the prizes basis points for the tournament ID
+        self.internal_add_prizes_to_tournament(&tournament_id, &basis_points_map);
+        
+        //index the tournament by its owner and state
+        self.internal_add_tournament_to_owner(&tournament.owner_id, &tournament_id);
+        self.internal_add_tournament_to_state(tournament.state, &tournament_id);
+        
+        //count the new tournament
+        self.stats.tournaments_total += 1;
+        *self.stats.state_counter(tournament.state) += 1;
         
         TournamentCreateLog{
             tournament_id: &tournament_id,
+            owner_id: &tournament.owner_id,
             players_number: &players_number,
             in_price: &in_price,
         }.emit();
+        
+        if guarantee > 0 {
+            self.guarantees_per_tournament.insert(&tournament_id, &guarantee);
+            self.stats.locked_balance += guarantee;
+            
+            TournamentGuaranteeLog{
+                tournament_id: &tournament_id,
+                owner_id: &tournament.owner_id,
+                amount: &guarantee.into(),
+            }.emit();
+        }
+    }
+
+    //change the tournament metadata, economic fields are locked once players have joined
+    fn tournament_update(&mut self, tournament_id: TournamentId, update: TournamentMetadataUpdate) {
+        let tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+        
+        //check the owner calls this method
+        assert_eq!(env::predecessor_account_id(), tournament.owner_id, "Owner's method");
+        
+        //check the tournament is active
+        assert_eq!(tournament.state, TournamentState::Open, "Tournament is inactive");
+        
+        let mut metadata = self.tournament_metadata_by_id.get(&tournament_id).unwrap();
+        
+        //collect the names of the changed fields for the event
+        let mut updated_fields: Vec<&str> = Vec::new();
+        
+        //cosmetic fields can be changed at any time
+        if let Some(name) = update.name {
+            metadata.name = name;
+            updated_fields.push("name");
+        }
+        if let Some(icon) = update.icon {
+            metadata.icon = non_empty(icon);
+            updated_fields.push("icon");
+        }
+        if let Some(description) = update.description {
+            metadata.description = non_empty(description);
+            updated_fields.push("description");
+        }
+        if let Some(game) = update.game {
+            metadata.game = non_empty(game);
+            updated_fields.push("game");
+        }
+        if update.reference.is_some() || update.reference_hash.is_some() {
+            metadata.reference = update.reference.and_then(non_empty);
+            metadata.reference_hash = update.reference_hash.filter(|hash| !hash.0.is_empty());
+            updated_fields.push("reference");
+        }
+        if let Some(links) = update.links {
+            metadata.links = if links.is_empty() { None } else { Some(links) };
+            updated_fields.push("links");
+        }
+        if let Some(extra) = update.extra {
+            metadata.extra = non_empty(extra);
+            updated_fields.push("extra");
+        }
+        
+        //the schedule and economic fields are locked once anyone has joined
+        let locked = self.internal_get_players_number_in_tournament(&tournament_id) > 0;
+        
+        if update.starts_at.is_some() || update.ends_at.is_some() {
+            assert!(!locked, "Schedule can't be changed after players have joined");
+            if update.starts_at.is_some() {
+                metadata.starts_at = update.starts_at;
+                updated_fields.push("starts_at");
+            }
+            if update.ends_at.is_some() {
+                metadata.ends_at = update.ends_at;
+                updated_fields.push("ends_at");
+            }
+            assert_valid_schedule(&metadata.starts_at, &metadata.ends_at);
+        }
+        
+        let old_players_number = metadata.players_number;
+        
+        if let Some(players_number) = update.players_number {
+            assert!(!locked, "Players number can't be changed after players have joined");
+            metadata.players_number = players_number;
+            updated_fields.push("players_number");
+            
+            //the payout tiers have to fit the new players number
+            if let Some(payout_tiers) = self.payout_tiers_per_tournament.get(&tournament_id) {
+                assert_valid_payout_tiers(&payout_tiers, players_number);
+            }
+        }
+        if let Some(in_price) = update.in_price {
+            assert!(!locked, "In price can't be changed after players have joined");
+            metadata.in_price = in_price.into();
+            updated_fields.push("in_price");
+        }
+        
+        if let Some(team_size) = update.team_size {
+            assert!(!locked, "Team size can't be changed after players have joined");
+            metadata.team_size = if team_size == 0 { None } else { Some(team_size) };
+            updated_fields.push("team_size");
+        }
+        
+        //the access can be changed while the registration is open
+        if let Some(access) = update.access {
+            if let Some(starts_at) = metadata.starts_at {
+                assert!(env::block_timestamp() < starts_at.0, "Access can't be changed after the registration closes");
+            }
+            metadata.access = access;
+            updated_fields.push("access");
+        }
+        
+        //the gates check the entrances coming after the update
+        if let Some(gates) = update.gates {
+            metadata.gates = if gates.is_empty() { None } else { Some(gates) };
+            updated_fields.push("gates");
+        }
+        
+        if let Some(basis_points_map) = update.basis_points_map {
+            assert!(!locked, "Prizes can't be changed after players have joined");
+            assert_valid_basis_points(&basis_points_map, metadata.players_number);
+            self.internal_replace_prizes_of_tournament(&tournament_id, &basis_points_map);
+            updated_fields.push("basis_points_map");
+        } else if metadata.players_number < old_players_number {
+            //the kept places have to fit the new players number
+            let prizes_map = self.winners_basis_points_per_tournament.get(&tournament_id).unwrap();
+            for place in prizes_map.keys() {
+                assert!(place <= metadata.players_number, "Place {} is out of the players number", place);
+            }
+        }
+        
+        assert!(!updated_fields.is_empty(), "Nothing to update");
+        
+        metadata.assert_valid();
+        
+        self.tournament_metadata_by_id.insert(&tournament_id, &metadata);
+        
+        TournamentUpdateLog{
+            tournament_id: &tournament_id,
+            updated_fields: &updated_fields,
+        }.emit();
     }
 
     //get the information for a specific tournament ID
@@ -135,18 +378,41 @@ impl TournamentContractCore for TournamentContract {
             //we'll get the metadata for that token
             let metadata = self.tournament_metadata_by_id.get(&tournament_id).unwrap();
             
-            let prizes = self.winners_percents_per_tournament.get(&tournament_id).unwrap();
+            let prizes = self.winners_basis_points_per_tournament.get(&tournament_id).unwrap();
+            
+            //the prizes of the places are the ones of the active payout tier if there is one
+            let active_payout_tier = self.internal_get_active_payout_tier(&tournament_id);
+            let place_prize = |place: u32| match &active_payout_tier {
+                Some((_, tier)) => tier.basis_points_map.get(&place).copied().unwrap_or(0),
+                None => prizes.get(&place).unwrap_or(0),
+            };
+            
+            let effective_prize_fond = self.internal_get_prize_pot(&tournament_id, &tournament);
+            
+            //read everything keyed by the tournament ID before it is moved into the view
+            let sponsors = self.internal_get_sponsors(&tournament_id).into_iter()
+                .map(|(account_id, amount)| JsonSponsor { account_id, amount: amount.into() })
+                .collect();
+            let guaranteed_prize_fond = self.guarantees_per_tournament.get(&tournament_id).map(U128);
+            let payout_tiers = self.payout_tiers_per_tournament.get(&tournament_id).unwrap_or_default();
             
             //we return the JsonToken (wrapped by Some since we return an option)
             Some(JsonTournament {
                 tournament_id,
                 owner_id: tournament.owner_id,
+                pending_owner_id: tournament.pending_owner_id,
                 metadata,
-                first_place_prize: (prizes.get(&1).unwrap() as u64).into(),
-                second_place_prize: (prizes.get(&2).unwrap() as u64).into(),
-                third_place_prize: (prizes.get(&3).unwrap() as u64).into(),
-                active: tournament.active,
+                first_place_prize: u64::from(place_prize(1)).into(),
+                second_place_prize: u64::from(place_prize(2)).into(),
+                third_place_prize: u64::from(place_prize(3)).into(),
+                active: tournament.state == TournamentState::Open,
+                state: tournament.state,
                 prize_fond: tournament.balance.into(),
+                sponsors,
+                guaranteed_prize_fond,
+                effective_prize_fond: effective_prize_fond.into(),
+                payout_tiers,
+                active_payout_tier: active_payout_tier.as_ref().map(|(index, _)| *index),
             })
         } else { 
             //if there wasn't a token ID in the tokens_by_id collection, we return None
@@ -154,51 +420,49 @@ impl TournamentContractCore for TournamentContract {
         }     
     }
     
-    //add player to the tournament with NEAR depositing
+    //add player to the tournament with NEAR depositing, invite code tournaments take the code
     //#[payable]
-    fn participate_tournament(&mut self, tournament_id: TournamentId) {    
+    fn participate_tournament(&mut self, tournament_id: TournamentId, invite_code: Option<String>) {    
         let account_id: &AccountId = &env::predecessor_account_id();
         
-        let attached_deposit: Balance = env::attached_deposit();  
-        
         if let Some(mut tournament) = self.tournaments_by_id.get(&tournament_id) {
-            //check activeness of the tournament
-            assert!(tournament.active, "Tournament is inactive");
-            
             //we'll get the metadata for that tournament
             let metadata = self.tournament_metadata_by_id.get(&tournament_id).unwrap();
             
-            //Check there are some free playses for the players in the tournament
-            assert!(
-                metadata.players_number-self.internal_get_players_number_in_tournament(&tournament_id)>0,
-                "Tournament is already full of players",
-            );
-            
-            //check the is enouph deposit attached to players account
-            assert!(attached_deposit >= metadata.in_price, "Deposit is too small. Attached: {}, Required: {}", attached_deposit, metadata.in_price);
-            
-            //check for double participation
-            assert!(self.internal_add_player_to_tournament(&tournament_id,&account_id), "Already in the tournament");
-            
-            //save the prize fond balanse of the tournament 
-            tournament.balance+=metadata.in_price;
-            self.tournaments_by_id.insert(&tournament_id, &tournament);
+            //teams take their seats with `register_team`
+            assert!(metadata.team_size.is_none(), "Team tournament, register a team");
             
-            TournamentEntranceLog{
-                partisipator_id: &account_id,
-                tournament_id: &tournament_id,                
-            }.emit();
-            
-            //get the refund amount from the attached deposit - required cost
-            let refund = attached_deposit - metadata.in_price;
-            
-            //if the refund is greater than 1 yocto NEAR, we refund the predecessor that amount
-            if refund > 1 {
-                Promise::new(env::predecessor_account_id()).transfer(refund);
-            }        
+            self.internal_enter_tournament(&tournament_id, &mut tournament, &metadata, account_id, account_id, invite_code);
         }        
     }
     
+    //add another player to the tournament paying the entrance, the refunds go back to the payer
+    //#[payable]
+    fn participate_tournament_for(&mut self, tournament_id: TournamentId, player_id: AccountId, invite_code: Option<String>) {
+        let payer_id = env::predecessor_account_id();
+        
+        let mut tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+        let metadata = self.tournament_metadata_by_id.get(&tournament_id).unwrap();
+        
+        //teams take their seats with `register_team`
+        assert!(metadata.team_size.is_none(), "Team tournament, register a team");
+        
+        //the player doesn't consent to the seat, so at least it has to be paid for
+        assert!(metadata.in_price > 0, "Free tournament, the players join themselves");
+        
+        self.internal_enter_tournament(&tournament_id, &mut tournament, &metadata, &player_id, &payer_id, invite_code);
+    }
+    
+    //get the account that paid for the seat of the player
+    fn display_payer(&self, tournament_id: TournamentId, player_id: AccountId) -> Option<AccountId> {
+        let players_set = self.players_per_tournament.get(&tournament_id)?;
+        if !players_set.contains(&player_id) {
+            return None;
+        }
+        
+        Some(self.internal_get_payer(&tournament_id, &player_id))
+    }
+    
     //get free playses in the tournament  
     fn display_freeplaces_in_tournament(&self, tournament_id: TournamentId) -> Option<U64> {                
         //if there is some tournament ID in the tournaments_by_id collection
@@ -210,7 +474,7 @@ impl TournamentContractCore for TournamentContract {
             let free_places = metadata.players_number-self.internal_get_players_number_in_tournament(&tournament_id);
             
             //return free places
-            Some((free_places as u64).into())
+            Some(u64::from(free_places).into())
             
         } else { 
             //if there wasn't a tournament_id ID in the tournaments_by_id collection, we return None
@@ -219,48 +483,938 @@ impl TournamentContractCore for TournamentContract {
     }
     
     //refunds the prizes for the winners 
-    fn reward_prizes(&mut self, tournament_id: TournamentId, winners_map: HashMap<u8,AccountId>) {        
+    fn reward_prizes(&mut self, tournament_id: TournamentId, winners_map: HashMap<u32,AccountId>) {        
         //if there is some tournament ID in the tournaments_by_id collection
         if let Some(mut tournament) = self.tournaments_by_id.get(&tournament_id) {            
             //check the owner calls this method
             assert_eq!(env::predecessor_account_id(), tournament.owner_id, "Owner's method");
             
             //check the tournament is active
-            assert!(tournament.active, "Tournament is inactive");            
+            assert_eq!(tournament.state, TournamentState::Open, "Tournament is inactive");            
+            
+            //payouts are blocked while paused
+            self.internal_assert_not_paused(&tournament_id);
+            
+            //top the prize fond up to the guarantee
+            self.internal_settle_guarantee(&tournament_id, &mut tournament, true);
+            
+            //the payout closes the registration, lock the payout tier matching the entrants, the payout and the view use it from now on
+            self.internal_lock_payout_tier(&tournament_id);
             
-            //get prizes values in persent for the places
-            let prizes_map = self.winners_percents_per_tournament.get(&tournament_id).unwrap();
+            //calculate the prizes of the winners
+            let plan = self.internal_payout_plan(&tournament_id, &tournament, winners_map);
+            let sum_reward = plan.paid_out;
             
-            //summarize the rewards
-            let mut sum_reward = 0;
+            //keep the prize of each winner for the event
+            let mut winners: Vec<TournamentWinnerLog> = Vec::new();
             
             //reward prizes
-            for (place,account) in winners_map {
-                //get percents to the place
-                let percents: u128 = prizes_map.get(&place).unwrap().into();
-                
-                //calculate the percents of the prize fond
-                let reward_amount = percent_calculation(&percents, &tournament.balance);
-                
+            for transfer in plan.transfers {
                 //refund the prize
-                Promise::new(account).transfer(reward_amount);
+                Promise::new(transfer.account_id.clone()).transfer(transfer.amount);
                 
-                //summarize the rewards
-                sum_reward+=reward_amount;
+                winners.push(TournamentWinnerLog {
+                    place: transfer.place,
+                    account_id: transfer.account_id,
+                    amount: transfer.amount.into(),
+                });
             }
             
             //decrease the prize fond of tournament 
             tournament.balance-=sum_reward;
             
+            //count the paid out prizes
+            self.stats.locked_balance -= sum_reward;
+            self.stats.paid_out += sum_reward;
+            
             //inactivate the tournament
-            tournament.active=false;
+            self.internal_set_tournament_state(&tournament_id, &mut tournament, TournamentState::Completed);
             
             self.tournaments_by_id.insert(&tournament_id, &tournament);        
             
             TournamentPrizesRewardLog{                
                 tournament_id: &tournament_id,    
-                rewarded_amount: &sum_reward,
+                rewarded_amount: &U128(sum_reward),
+                winners: &winners,
             }.emit();
         }                 
     }    
+    
+    //get the prizes `reward_prizes` would transfer to the winners
+    fn preview_rewards(&self, tournament_id: TournamentId, winners_map: HashMap<u32,AccountId>) -> JsonRewardsPreview {
+        let tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+        
+        //the same checks as the payout, except the owner one: the views have no caller
+        assert_eq!(tournament.state, TournamentState::Open, "Tournament is inactive");
+        self.internal_assert_not_paused(&tournament_id);
+        
+        let plan = self.internal_payout_plan(&tournament_id, &tournament, winners_map);
+        
+        JsonRewardsPreview {
+            winners: plan.transfers.into_iter()
+                .map(|transfer| JsonPrize {
+                    place: transfer.place,
+                    account_id: transfer.account_id,
+                    amount: transfer.amount.into(),
+                })
+                .collect(),
+            rewarded_amount: plan.paid_out.into(),
+            remainder: plan.remainder.into(),
+        }
+    }
+    
+    //cancel the tournament refunding the players and the sponsors
+    fn cancel_tournament(&mut self, tournament_id: TournamentId) {
+        let mut tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+        
+        //check the owner calls this method
+        assert_eq!(env::predecessor_account_id(), tournament.owner_id, "Owner's method");
+        
+        //check the tournament is active
+        assert_eq!(tournament.state, TournamentState::Open, "Tournament is inactive");
+        
+        //the cancel closes the registration, lock the payout tier matching the entrants like the payout does
+        self.internal_lock_payout_tier(&tournament_id);
+        
+        let metadata = self.tournament_metadata_by_id.get(&tournament_id).unwrap();
+        
+        //the deposits go back to the accounts that paid for the players, free tournaments have nothing to refund
+        let mut refunds: Vec<(AccountId, Balance)> = Vec::new();
+        if metadata.in_price > 0 {
+            if let Some(players_set) = self.players_per_tournament.get(&tournament_id) {
+                for player_id in players_set.iter() {
+                    refunds.push((self.internal_get_payer(&tournament_id, &player_id), metadata.in_price));
+                }
+            }
+        }
+        
+        //the sponsors get their contributions back
+        refunds.extend(self.internal_get_sponsors(&tournament_id));
+        
+        for (account_id, amount) in refunds.iter() {
+            Promise::new(account_id.clone()).transfer(*amount);
+        }
+        
+        let refunded_amount: Balance = refunds.iter().map(|(_, amount)| amount).sum();
+        
+        //decrease the prize fond of tournament 
+        tournament.balance -= refunded_amount;
+        self.stats.locked_balance -= refunded_amount;
+        
+        self.internal_set_tournament_state(&tournament_id, &mut tournament, TournamentState::Cancelled);
+        
+        self.tournaments_by_id.insert(&tournament_id, &tournament);
+        
+        TournamentCancelLog{
+            tournament_id: &tournament_id,
+            refunded_amount: &refunded_amount.into(),
+        }.emit();
+        
+        let amounts: Vec<U128> = refunds.iter().map(|(_, amount)| U128(*amount)).collect();
+        let refund_logs: Vec<TournamentRefundLog> = refunds.iter().zip(amounts.iter())
+            .map(|((account_id, _), amount)| TournamentRefundLog{
+                tournament_id: &tournament_id,
+                account_id,
+                amount,
+            })
+            .collect();
+        if !refund_logs.is_empty() {
+            TournamentRefundLog::emit_many(&refund_logs);
+        }
+        
+        //the whole guarantee goes back to the owner
+        self.internal_settle_guarantee(&tournament_id, &mut tournament, false);
+    }
+    
+    //leave the scheduled tournament before the registration closes getting the deposit back
+    fn leave_tournament(&mut self, tournament_id: TournamentId) {
+        let account_id = env::predecessor_account_id();
+        
+        self.internal_leave_tournament(&tournament_id, &account_id);
+    }
+    
+    //give up the seat paid for another player before the registration closes getting the deposit back
+    fn withdraw_sponsored_seat(&mut self, tournament_id: TournamentId, player_id: AccountId) {
+        let payer_id = env::predecessor_account_id();
+        
+        //the seats of the players paying for themselves are left with `leave_tournament`
+        assert!(
+            payer_id != player_id && self.internal_get_payer(&tournament_id, &player_id) == payer_id,
+            "Not the payer of the seat"
+        );
+        
+        self.internal_leave_tournament(&tournament_id, &player_id);
+    }
+    
+    //withdraw the rest of the prize fond of the completed tournament to the owner
+    fn withdraw_fees(&mut self, tournament_id: TournamentId) {
+        let mut tournament = self.tournaments_by_id.get(&tournament_id).expect("No tournament");
+        
+        //check the owner calls this method
+        assert_eq!(env::predecessor_account_id(), tournament.owner_id, "Owner's method");
+        
+        //check the prizes are rewarded
+        assert_eq!(tournament.state, TournamentState::Completed, "Tournament isn't completed");
+        
+        //payouts are blocked while paused
+        self.internal_assert_not_paused(&tournament_id);
+        
+        let amount = tournament.balance;
+        assert!(amount > 0, "Nothing to withdraw");
+        
+        tournament.balance = 0;
+        self.tournaments_by_id.insert(&tournament_id, &tournament);
+        
+        self.stats.locked_balance -= amount;
+        
+        Promise::new(tournament.owner_id.clone()).transfer(amount);
+        
+        TournamentFeesWithdrawLog{
+            tournament_id: &tournament_id,
+            owner_id: &tournament.owner_id,
+            amount: &amount.into(),
+        }.emit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_parser::*;
+    use crate::tournament::testing::*;
+    use crate::tournament::TournamentAccess;
+    use near_sdk::test_utils::accounts;
+
+    const TOURNAMENT_ID: &str = "tournament-1";
+
+    //a tournament of accounts(1) for two players
+    fn setup() -> TournamentContract {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(1), 2, IN_PRICE);
+        contract
+    }
+
+    //a full tournament of accounts(1) with accounts(2) and accounts(3) in it
+    fn setup_full() -> TournamentContract {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+        join(&mut contract, TOURNAMENT_ID, accounts(3));
+        contract
+    }
+
+    fn winners_map() -> HashMap<u32,AccountId> {
+        let mut winners_map = HashMap::new();
+        winners_map.insert(1, accounts(3));
+        winners_map.insert(2, accounts(2));
+        winners_map
+    }
+
+    #[test]
+    fn create_tournament_logs_event() {
+        let contract = setup();
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.owner_id, accounts(1));
+        assert_eq!(tournament.state, TournamentState::Open);
+        assert_eq!(tournament.metadata.name, "TOURNAMENT-1");
+        assert_eq!(tournament.metadata.in_price, IN_PRICE);
+        assert_eq!(tournament.first_place_prize, U64(6_000));
+        assert_eq!(tournament.second_place_prize, U64(3_000));
+        assert_eq!(tournament.third_place_prize, U64(0));
+        assert_eq!(tournament.prize_fond, U128(0));
+
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentCreate(vec![TournamentCreateData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            owner_id: Some(accounts(1)),
+            players_number: 2,
+            in_price: U128(IN_PRICE),
+        }])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tornament already exists")]
+    fn create_duplicate_tournament() {
+        let mut contract = setup();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(4), 4, IN_PRICE);
+    }
+
+    #[test]
+    fn create_free_tournament() {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(1), 2, 0);
+
+        //the attached deposit is refunded
+        set_context(accounts(2), IN_PRICE);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.prize_fond, U128(0));
+        assert!(tournament.sponsors.is_empty());
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Place 2 is out of the players number")]
+    fn create_prize_out_of_players_number() {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(1), 1, IN_PRICE);
+    }
+
+    #[test]
+    fn update_tournament() {
+        let mut contract = setup();
+
+        set_context(accounts(1), 0);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            name: Some("Final".to_string()),
+            players_number: Some(4),
+            ..Default::default()
+        });
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.metadata.name, "Final");
+        assert_eq!(tournament.metadata.players_number, 4);
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentUpdate(vec![TournamentUpdateData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            updated_fields: vec!["name".to_string(), "players_number".to_string()],
+        }])]);
+    }
+
+    #[test]
+    fn create_tournament_above_255_players() {
+        let mut contract = new_contract();
+        let mut basis_points_map = HashMap::new();
+        basis_points_map.insert(1, 2_500);
+        basis_points_map.insert(300, 5);
+        
+        set_context(accounts(1), 0);
+        contract.tournament_create(TournamentCreateArgs::new(
+            TOURNAMENT_ID.to_string(), "Qualifier".to_string(), 1_000, IN_PRICE, accounts(1), basis_points_map,
+        ));
+        
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.metadata.players_number, 1_000);
+        assert_eq!(tournament.first_place_prize, U64(2_500));
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(1_000)));
+    }
+
+    #[test]
+    fn update_prizes_replaces_places() {
+        let mut contract = setup();
+        let mut basis_points_map = HashMap::new();
+        basis_points_map.insert(1, 9_000);
+
+        set_context(accounts(1), 0);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            basis_points_map: Some(basis_points_map),
+            ..Default::default()
+        });
+        
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.first_place_prize, U64(9_000));
+        assert_eq!(tournament.second_place_prize, U64(0));
+        
+        //the second place has no prize anymore, so one seat is enough
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            players_number: Some(1),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Access can't be changed after the registration closes")]
+    fn update_access_after_start() {
+        let mut contract = setup();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+
+        set_context_at(accounts(1), 0, 1_000);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            access: Some(TournamentAccess::InviteCode),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Place 2 is out of the players number")]
+    fn update_players_number_below_prizes() {
+        let mut contract = setup();
+
+        set_context(accounts(1), 0);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            players_number: Some(1),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner's method")]
+    fn update_tournament_by_other_account() {
+        let mut contract = setup();
+
+        set_context(accounts(2), 0);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            name: Some("Final".to_string()),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "In price can't be changed after players have joined")]
+    fn update_in_price_after_entrance() {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+
+        set_context(accounts(1), 0);
+        contract.tournament_update(TOURNAMENT_ID.to_string(), TournamentMetadataUpdate {
+            in_price: Some(U128(1)),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn display_unknown_tournament() {
+        let contract = setup();
+        assert!(contract.display_tournament("tournament-2".to_string()).is_none());
+        assert!(contract.display_freeplaces_in_tournament("tournament-2".to_string()).is_none());
+    }
+
+    #[test]
+    fn participate_with_exact_deposit() {
+        let mut contract = setup();
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(2)));
+
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(1)));
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(IN_PRICE));
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentEntrance(vec![TournamentEntranceData {
+            partisipator_id: accounts(2),
+            tournament_id: TOURNAMENT_ID.to_string(),
+            payer_id: None,
+        }])]);
+    }
+
+    #[test]
+    fn participate_with_bigger_deposit() {
+        let mut contract = setup();
+
+        set_context(accounts(2), IN_PRICE * 3);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+
+        //only the in price goes to the prize fond, the rest is refunded
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(IN_PRICE));
+        assert_eq!(contract.stats.locked_balance, IN_PRICE);
+    }
+
+    #[test]
+    fn participate_for_another_player() {
+        let mut contract = setup();
+
+        set_context(accounts(4), IN_PRICE * 2);
+        contract.participate_tournament_for(TOURNAMENT_ID.to_string(), accounts(2), None);
+
+        assert_eq!(contract.display_payer(TOURNAMENT_ID.to_string(), accounts(2)), Some(accounts(4)));
+        assert_eq!(contract.display_payer(TOURNAMENT_ID.to_string(), accounts(4)), None);
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(IN_PRICE));
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentEntrance(vec![TournamentEntranceData {
+            partisipator_id: accounts(2),
+            tournament_id: TOURNAMENT_ID.to_string(),
+            payer_id: Some(accounts(4)),
+        }])]);
+
+        //players paying for themselves are their own payers
+        join(&mut contract, TOURNAMENT_ID, accounts(3));
+        assert_eq!(contract.display_payer(TOURNAMENT_ID.to_string(), accounts(3)), Some(accounts(3)));
+    }
+
+    #[test]
+    fn leave_sponsored_seat_refunds_payer() {
+        let mut contract = setup();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+
+        set_context(accounts(4), IN_PRICE);
+        contract.participate_tournament_for(TOURNAMENT_ID.to_string(), accounts(2), None);
+
+        set_context(accounts(2), 0);
+        contract.leave_tournament(TOURNAMENT_ID.to_string());
+
+        assert_eq!(contract.display_payer(TOURNAMENT_ID.to_string(), accounts(2)), None);
+        assert_eq!(logged_events()[1], TournamentEventData::TournamentRefund(vec![TournamentRefundData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            account_id: accounts(4),
+            amount: U128(IN_PRICE),
+        }]));
+
+        //the player paying the next time gets the next refund
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+        assert_eq!(contract.display_payer(TOURNAMENT_ID.to_string(), accounts(2)), Some(accounts(2)));
+    }
+
+    #[test]
+    fn cancel_refunds_payers_and_reward_pays_players() {
+        let mut contract = setup();
+
+        set_context(accounts(4), IN_PRICE);
+        contract.participate_tournament_for(TOURNAMENT_ID.to_string(), accounts(2), None);
+        join(&mut contract, TOURNAMENT_ID, accounts(3));
+
+        //the prizes go to the players
+        let preview = contract.preview_rewards(TOURNAMENT_ID.to_string(), winners_map());
+        assert_eq!(preview.winners[1].account_id, accounts(2));
+
+        set_context(accounts(1), 0);
+        contract.cancel_tournament(TOURNAMENT_ID.to_string());
+
+        if let TournamentEventData::TournamentRefund(refunds) = &logged_events()[1] {
+            let mut refunded: Vec<AccountId> = refunds.iter().map(|refund| refund.account_id.clone()).collect();
+            refunded.sort();
+            assert_eq!(refunded, vec![accounts(3), accounts(4)]);
+        } else {
+            panic!("Expected the refunds");
+        }
+    }
+
+    #[test]
+    fn payer_withdraws_sponsored_seat() {
+        let mut contract = setup();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+
+        set_context(accounts(4), IN_PRICE);
+        contract.participate_tournament_for(TOURNAMENT_ID.to_string(), accounts(2), None);
+
+        set_context(accounts(4), 0);
+        contract.withdraw_sponsored_seat(TOURNAMENT_ID.to_string(), accounts(2));
+
+        assert_eq!(contract.display_payer(TOURNAMENT_ID.to_string(), accounts(2)), None);
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(2)));
+        assert_eq!(logged_events()[1], TournamentEventData::TournamentRefund(vec![TournamentRefundData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            account_id: accounts(4),
+            amount: U128(IN_PRICE),
+        }]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not the payer of the seat")]
+    fn withdraw_seat_paid_by_player() {
+        let mut contract = setup();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+
+        set_context(accounts(4), 0);
+        contract.withdraw_sponsored_seat(TOURNAMENT_ID.to_string(), accounts(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Free tournament, the players join themselves")]
+    fn participate_for_free_tournament() {
+        let mut contract = new_contract();
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(1), 2, 0);
+
+        set_context(accounts(4), 0);
+        contract.participate_tournament_for(TOURNAMENT_ID.to_string(), accounts(2), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Team tournament, register a team")]
+    fn participate_for_team_tournament() {
+        let mut contract = new_contract();
+        create_team_tournament(&mut contract, TOURNAMENT_ID, accounts(1), 2, 2);
+
+        set_context(accounts(4), IN_PRICE);
+        contract.participate_tournament_for(TOURNAMENT_ID.to_string(), accounts(2), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Deposit is too small. Attached: 99, Required: 100")]
+    fn participate_with_small_deposit() {
+        let mut contract = setup();
+
+        set_context(accounts(2), IN_PRICE - 1);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Deposit is too small. Attached: 0, Required: 100")]
+    fn participate_without_deposit() {
+        let mut contract = setup();
+
+        set_context(accounts(2), 0);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Already in the tournament")]
+    fn participate_twice() {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament is already full of players")]
+    fn participate_full_tournament() {
+        let mut contract = setup_full();
+        join(&mut contract, TOURNAMENT_ID, accounts(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Registration is closed")]
+    fn participate_after_start() {
+        let mut contract = new_contract();
+        contract.tournament_create(
+            TournamentCreateArgs::new(TOURNAMENT_ID.to_string(), "Scheduled".to_string(), 2, IN_PRICE, accounts(1), basis_points_map())
+                .schedule(Some(1_000), Some(2_000)),
+        );
+
+        set_context_at(accounts(2), IN_PRICE, 1_000);
+        contract.participate_tournament(TOURNAMENT_ID.to_string(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament is paused")]
+    fn participate_paused_tournament() {
+        let mut contract = setup();
+        contract.paused_tournaments.insert(&TOURNAMENT_ID.to_string());
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+    }
+
+    #[test]
+    fn reward_prizes_by_owner() {
+        let mut contract = setup_full();
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.state, TournamentState::Completed);
+        assert!(!tournament.active);
+        //10 percents of the prize fond are left for the owner
+        assert_eq!(tournament.prize_fond, U128(20));
+        assert_eq!(contract.stats.paid_out, 180);
+        assert_eq!(contract.stats.locked_balance, 20);
+
+        let mut events = logged_events();
+        assert_eq!(events.len(), 1);
+        if let TournamentEventData::TournamentPrizesReward(data) = &mut events[0] {
+            assert_eq!(data[0].rewarded_amount, U128(180));
+            data[0].winners.sort_by_key(|winner| winner.place);
+            assert_eq!(data[0].winners, vec![
+                TournamentWinnerData { place: 1, account_id: accounts(3), amount: U128(120) },
+                TournamentWinnerData { place: 2, account_id: accounts(2), amount: U128(60) },
+            ]);
+        } else {
+            panic!("Unexpected event {:?}", events[0]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner's method")]
+    fn reward_prizes_by_other_account() {
+        let mut contract = setup_full();
+
+        set_context(accounts(2), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament is inactive")]
+    fn reward_prizes_twice() {
+        let mut contract = setup_full();
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+    }
+
+    #[test]
+    #[should_panic(expected = "No prize for the place 3")]
+    fn reward_prizes_place_without_prize() {
+        let mut contract = setup_full();
+
+        let mut winners_map = winners_map();
+        winners_map.insert(3, accounts(4));
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map);
+    }
+
+    #[test]
+    fn preview_rewards_matches_reward() {
+        let mut contract = setup_full();
+
+        let preview = contract.preview_rewards(TOURNAMENT_ID.to_string(), winners_map());
+        assert_eq!(preview.rewarded_amount, U128(180));
+        assert_eq!(preview.remainder, U128(20));
+        assert_eq!(preview.winners.iter().map(|prize| prize.amount).collect::<Vec<_>>(), vec![U128(120), U128(60)]);
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, preview.remainder);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament is paused")]
+    fn preview_rewards_paused_tournament() {
+        let mut contract = setup_full();
+        contract.paused_tournaments.insert(&TOURNAMENT_ID.to_string());
+
+        contract.preview_rewards(TOURNAMENT_ID.to_string(), winners_map());
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament is inactive")]
+    fn preview_rewards_of_completed_tournament() {
+        let mut contract = setup_full();
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+
+        contract.preview_rewards(TOURNAMENT_ID.to_string(), winners_map());
+    }
+
+    #[test]
+    #[should_panic(expected = "Contract is paused")]
+    fn reward_prizes_paused_contract() {
+        let mut contract = setup_full();
+        contract.paused = true;
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament is inactive")]
+    fn participate_completed_tournament() {
+        let mut contract = setup();
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), HashMap::new());
+
+        join(&mut contract, TOURNAMENT_ID, accounts(3));
+    }
+
+    #[test]
+    fn cancel_tournament_refunds_players() {
+        let mut contract = setup_full();
+
+        set_context(accounts(1), 0);
+        contract.cancel_tournament(TOURNAMENT_ID.to_string());
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.state, TournamentState::Cancelled);
+        assert_eq!(tournament.prize_fond, U128(0));
+        assert_eq!(contract.stats.locked_balance, 0);
+        assert_eq!(contract.stats.tournaments_cancelled, 1);
+        assert_eq!(contract.stats.tournaments_open, 0);
+
+        let events = logged_events();
+        assert_eq!(events[0], TournamentEventData::TournamentCancel(vec![TournamentCancelData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            refunded_amount: U128(2 * IN_PRICE),
+        }]));
+        if let TournamentEventData::TournamentRefund(refunds) = &events[1] {
+            let mut refunded: Vec<AccountId> = refunds.iter().map(|refund| refund.account_id.clone()).collect();
+            refunded.sort();
+            assert_eq!(refunded, vec![accounts(2), accounts(3)]);
+            assert!(refunds.iter().all(|refund| refund.amount == U128(IN_PRICE)));
+        } else {
+            panic!("Unexpected event {:?}", events[1]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner's method")]
+    fn cancel_tournament_by_other_account() {
+        let mut contract = setup_full();
+
+        set_context(accounts(2), 0);
+        contract.cancel_tournament(TOURNAMENT_ID.to_string());
+    }
+
+    #[test]
+    fn leave_tournament_refunds_deposit() {
+        let mut contract = setup_full();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+
+        set_context(accounts(2), 0);
+        contract.leave_tournament(TOURNAMENT_ID.to_string());
+
+        assert_eq!(contract.display_freeplaces_in_tournament(TOURNAMENT_ID.to_string()), Some(U64(1)));
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(IN_PRICE));
+        assert_eq!(contract.stats.players_total, 1);
+        assert_eq!(logged_events(), vec![
+            TournamentEventData::TournamentLeave(vec![TournamentLeaveData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                player_id: accounts(2),
+            }]),
+            TournamentEventData::TournamentRefund(vec![TournamentRefundData {
+                tournament_id: TOURNAMENT_ID.to_string(),
+                account_id: accounts(2),
+                amount: U128(IN_PRICE),
+            }]),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament without start time can't be left")]
+    fn leave_unscheduled_tournament() {
+        let mut contract = setup_full();
+
+        set_context(accounts(2), 0);
+        contract.leave_tournament(TOURNAMENT_ID.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Registration is closed")]
+    fn leave_tournament_after_start() {
+        let mut contract = setup_full();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+
+        set_context_at(accounts(2), 0, 1_000);
+        contract.leave_tournament(TOURNAMENT_ID.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Not in the tournament")]
+    fn leave_tournament_without_entrance() {
+        let mut contract = setup();
+        schedule_start(&mut contract, TOURNAMENT_ID, 1_000);
+
+        set_context(accounts(2), 0);
+        contract.leave_tournament(TOURNAMENT_ID.to_string());
+    }
+
+    #[test]
+    fn withdraw_fees_after_reward() {
+        let mut contract = setup_full();
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+
+        set_context(accounts(1), 0);
+        contract.withdraw_fees(TOURNAMENT_ID.to_string());
+
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().prize_fond, U128(0));
+        assert_eq!(contract.stats.locked_balance, 0);
+        assert_eq!(logged_events(), vec![TournamentEventData::TournamentFeesWithdraw(vec![TournamentFeesWithdrawData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            owner_id: accounts(1),
+            amount: U128(20),
+        }])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Nothing to withdraw")]
+    fn withdraw_fees_twice() {
+        let mut contract = setup_full();
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+        contract.withdraw_fees(TOURNAMENT_ID.to_string());
+        contract.withdraw_fees(TOURNAMENT_ID.to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Tournament isn't completed")]
+    fn withdraw_fees_of_open_tournament() {
+        let mut contract = setup_full();
+
+        set_context(accounts(1), 0);
+        contract.withdraw_fees(TOURNAMENT_ID.to_string());
+    }
+
+    //a full tournament of accounts(1) guaranteeing the prize fond with accounts(2) and accounts(3) in it
+    fn setup_guaranteed(guarantee: u128) -> TournamentContract {
+        let mut contract = new_contract();
+        set_context(accounts(1), guarantee);
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(1), 2, IN_PRICE);
+        join(&mut contract, TOURNAMENT_ID, accounts(2));
+        join(&mut contract, TOURNAMENT_ID, accounts(3));
+        contract
+    }
+
+    #[test]
+    fn guarantee_covers_short_prize_fond() {
+        let mut contract = setup_guaranteed(1_000);
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.guaranteed_prize_fond, Some(U128(1_000)));
+        assert_eq!(tournament.effective_prize_fond, U128(1_000));
+        assert_eq!(tournament.prize_fond, U128(2 * IN_PRICE));
+        assert_eq!(contract.stats.locked_balance, 1_200);
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+
+        //the winners share the guaranteed prize fond, the unused part of the guarantee is returned
+        let events = logged_events();
+        assert_eq!(events[0], TournamentEventData::TournamentGuaranteeSettle(vec![TournamentGuaranteeSettleData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            owner_id: accounts(1),
+            covered: U128(800),
+            returned: U128(200),
+        }]));
+        if let TournamentEventData::TournamentPrizesReward(data) = &events[1] {
+            assert_eq!(data[0].rewarded_amount, U128(900));
+        } else {
+            panic!("Unexpected event {:?}", events[1]);
+        }
+
+        let tournament = contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap();
+        assert_eq!(tournament.prize_fond, U128(100));
+        assert_eq!(tournament.effective_prize_fond, U128(100));
+        assert_eq!(contract.stats.locked_balance, 100);
+    }
+
+    #[test]
+    fn unused_guarantee_goes_back_to_owner() {
+        let mut contract = setup_guaranteed(150);
+
+        //the entry fees are above the guarantee
+        assert_eq!(contract.display_tournament(TOURNAMENT_ID.to_string()).unwrap().effective_prize_fond, U128(2 * IN_PRICE));
+        assert_eq!(contract.preview_rewards(TOURNAMENT_ID.to_string(), winners_map()).rewarded_amount, U128(180));
+
+        set_context(accounts(1), 0);
+        contract.reward_prizes(TOURNAMENT_ID.to_string(), winners_map());
+
+        assert_eq!(logged_events()[0], TournamentEventData::TournamentGuaranteeSettle(vec![TournamentGuaranteeSettleData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            owner_id: accounts(1),
+            covered: U128(0),
+            returned: U128(150),
+        }]));
+        assert_eq!(contract.stats.paid_out, 180);
+        assert_eq!(contract.stats.locked_balance, 20);
+    }
+
+    #[test]
+    fn cancel_returns_guarantee() {
+        let mut contract = setup_guaranteed(1_000);
+
+        set_context(accounts(1), 0);
+        contract.cancel_tournament(TOURNAMENT_ID.to_string());
+
+        let events = logged_events();
+        assert_eq!(events[0], TournamentEventData::TournamentCancel(vec![TournamentCancelData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            refunded_amount: U128(2 * IN_PRICE),
+        }]));
+        assert_eq!(events[2], TournamentEventData::TournamentGuaranteeSettle(vec![TournamentGuaranteeSettleData {
+            tournament_id: TOURNAMENT_ID.to_string(),
+            owner_id: accounts(1),
+            covered: U128(0),
+            returned: U128(1_000),
+        }]));
+        assert_eq!(contract.stats.locked_balance, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner can guarantee the prize fond")]
+    fn guarantee_by_other_account() {
+        let mut contract = new_contract();
+        set_context(accounts(0), 1_000);
+        create_tournament(&mut contract, TOURNAMENT_ID, accounts(1), 2, IN_PRICE);
+    }
 }
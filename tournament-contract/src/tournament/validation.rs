@@ -0,0 +1,82 @@
+//! Checks of the tournament arguments shared by the contract and the off-chain tools.
+//!
+//! The contract panics with the returned message, the tools report it before sending
+//! the transaction. `TournamentMetadata::validate` checks the metadata the same way.
+
+use near_sdk::json_types::U64;
+use near_sdk::AccountId;
+use std::collections::{HashMap, HashSet};
+use crate::payout::BASIS_POINTS_TOTAL;
+use crate::tournament::{PayoutTier, MAX_PAYOUT_TIERS_NUMBER, MAX_TEAM_NAME_LENGTH};
+
+//return the formatted error when the condition doesn't hold
+macro_rules! ensure {
+    ($condition: expr, $($message: tt)+) => {
+        if !$condition {
+            return Err(format!($($message)+));
+        }
+    };
+}
+
+//checks the places of the basis points map and that it doesn't distribute more than the prize fond
+pub fn check_basis_points(basis_points_map: &HashMap<u32,u16>, players_number: u32) -> Result<(), String> {
+    let mut sum_basis_points: u128 = 0;
+    for (place, basis_points) in basis_points_map {
+        ensure!(*place > 0 && *place <= players_number, "Place {} is out of the players number", place);
+        sum_basis_points += *basis_points as u128;
+    }
+    ensure!(sum_basis_points <= BASIS_POINTS_TOTAL, "Prizes are more than 100 percents");
+    Ok(())
+}
+
+//checks the entrants ranges of the tiers sorted by their minimums don't overlap and fit the players number,
+//the places of each tier have to be filled by its minimum of entrants
+pub fn check_payout_tiers(payout_tiers: &[PayoutTier], players_number: u32) -> Result<(), String> {
+    ensure!(payout_tiers.len() <= MAX_PAYOUT_TIERS_NUMBER, "More than {} payout tiers", MAX_PAYOUT_TIERS_NUMBER);
+    
+    let mut previous_max: u32 = 0;
+    for tier in payout_tiers {
+        let range = format!("{}-{}", tier.min_entrants, tier.max_entrants);
+        ensure!(tier.min_entrants > 0 && tier.min_entrants <= tier.max_entrants, "Entrants range {} is invalid", range);
+        ensure!(tier.max_entrants <= players_number, "Entrants range {} is out of the players number", range);
+        ensure!(tier.min_entrants > previous_max, "Entrants range {} overlaps the previous one", range);
+        check_basis_points(&tier.basis_points_map, tier.min_entrants)?;
+        previous_max = tier.max_entrants;
+    }
+    Ok(())
+}
+
+//checks the tournament ends after it starts
+pub fn check_schedule(starts_at: &Option<U64>, ends_at: &Option<U64>) -> Result<(), String> {
+    if let (Some(starts_at), Some(ends_at)) = (starts_at, ends_at) {
+        ensure!(starts_at.0 < ends_at.0, "Tournament should end after it starts");
+    }
+    Ok(())
+}
+
+//checks each place of the winners has a prize
+pub fn check_winners<F>(winners_map: &HashMap<u32,AccountId>, has_prize: F) -> Result<(), String>
+    where F: Fn(u32) -> bool
+{
+    for place in winners_map.keys() {
+        ensure!(has_prize(*place), "No prize for the place {}", place);
+    }
+    Ok(())
+}
+
+//checks the team fits the team size and each member has a prize share
+pub fn check_team(name: &str, members: &[AccountId], shares: &[u16], team_size: u8) -> Result<(), String> {
+    ensure!(!name.is_empty(), "Team name is empty");
+    ensure!(name.len() <= MAX_TEAM_NAME_LENGTH, "Team name is longer than {} bytes", MAX_TEAM_NAME_LENGTH);
+    ensure!(!members.is_empty(), "Team has no members");
+    ensure!(members.len() <= team_size as usize, "Team has more than {} members", team_size);
+    
+    let mut unique = HashSet::new();
+    for member in members {
+        ensure!(unique.insert(member), "{} is in the team twice", member);
+    }
+    
+    ensure!(shares.len() == members.len(), "Shares should be given for each member");
+    ensure!(shares.iter().any(|share| *share > 0), "Shares are all zero");
+    Ok(())
+}
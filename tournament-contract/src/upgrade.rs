@@ -0,0 +1,252 @@
+//! Self-upgrade of the contract by its owner.
+//!
+//! The new wasm code is passed as the raw input of the call (not JSON), e.g.
+//! `near call $CONTRACT stage_upgrade --base64 $(base64 -w0 out/main.wasm)`.
+//! With no upgrade delay the owner can `upgrade` at once. With a delay the code is staged
+//! first, so the players can see the upgrade coming, and applied once the delay has passed.
+//! Both ways deploy the code to the contract account and call `migrate` after it.
+//!
+//! The time the staged code can be applied is fixed when it is staged. A longer delay takes
+//! effect at once, a shorter one only after the current delay has passed, so the owner can't
+//! shorten the delay and upgrade before the players could see the upgrade coming.
+
+use crate::*;
+use near_sdk::{Gas, Promise};
+use near_sdk::serde::{Deserialize, Serialize};
+use crate::tournament::events::{ContractUpgradeStageLog, ContractUpgradeCancelLog, ContractUpgradeLog};
+
+/// Gas kept for the upgrade call itself, the rest is attached to `migrate`
+const GAS_FOR_UPGRADE: Gas = Gas(20_000_000_000_000);
+
+const STAGED_CODE_KEY: &[u8] = b"STAGED_CODE";
+const STAGED_AT_KEY: &[u8] = b"STAGED_AT";
+const APPLY_AFTER_KEY: &[u8] = b"APPLY_AFTER";
+const UPGRADE_DELAY_KEY: &[u8] = b"UPGRADE_DELAY";
+//the shorter delay and the time it takes effect
+const PENDING_DELAY_KEY: &[u8] = b"PENDING_UPGRADE_DELAY";
+
+//The staged upgrade returned from the view call.
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonStagedUpgrade {
+    //sha256 hash of the staged wasm code
+    pub code_hash: Base64VecU8,
+    pub staged_at: U64,
+    //the upgrade can be applied from this time (nanoseconds since epoch)
+    pub apply_after: U64,
+}
+
+fn read_u64(key: &[u8]) -> Option<u64> {
+    env::storage_read(key).map(|bytes| u64::try_from_slice(&bytes).unwrap())
+}
+
+fn write_u64(key: &[u8], value: u64) {
+    env::storage_write(key, &value.try_to_vec().unwrap());
+}
+
+fn upgrade_delay() -> u64 {
+    if let Some(bytes) = env::storage_read(PENDING_DELAY_KEY) {
+        let (delay, effective_at) = <(u64, u64)>::try_from_slice(&bytes).unwrap();
+        if env::block_timestamp() >= effective_at {
+            return delay;
+        }
+    }
+    read_u64(UPGRADE_DELAY_KEY).unwrap_or(0)
+}
+
+//deploy the code to the contract account and migrate the state after it
+fn deploy_and_migrate(code: Vec<u8>) -> Promise {
+    let code_hash = Base64VecU8(env::sha256(&code));
+
+    ContractUpgradeLog{
+        code_hash: &code_hash,
+    }.emit();
+
+    Promise::new(env::current_account_id())
+        .deploy_contract(code)
+        .function_call(
+            "migrate".to_string(),
+            Vec::new(),
+            0,
+            env::prepaid_gas() - env::used_gas() - GAS_FOR_UPGRADE,
+        )
+}
+
+#[near_bindgen]
+impl Contract {
+    //set the time between staging an upgrade and applying it (nanoseconds),
+    //a shorter delay takes effect once the current delay has passed
+    pub fn set_upgrade_delay(&mut self, delay: U64) {
+        self.assert_owner();
+
+        let current_delay = upgrade_delay();
+        if delay.0 >= current_delay {
+            write_u64(UPGRADE_DELAY_KEY, delay.0);
+            env::storage_remove(PENDING_DELAY_KEY);
+        } else {
+            write_u64(UPGRADE_DELAY_KEY, current_delay);
+            let effective_at = env::block_timestamp() + current_delay;
+            env::storage_write(PENDING_DELAY_KEY, &(delay.0, effective_at).try_to_vec().unwrap());
+        }
+    }
+
+    //get the time between staging an upgrade and applying it (nanoseconds)
+    pub fn upgrade_delay(&self) -> U64 {
+        upgrade_delay().into()
+    }
+
+    //deploy the code from the input at once, only when there is no upgrade delay
+    pub fn upgrade(&self) -> Promise {
+        self.assert_owner();
+        assert_eq!(upgrade_delay(), 0, "Upgrades are delayed, stage the upgrade first");
+
+        let code = env::input().expect("No code in the input");
+        assert!(!code.is_empty(), "No code in the input");
+
+        deploy_and_migrate(code)
+    }
+
+    //stage the code from the input to be deployed after the upgrade delay
+    pub fn stage_upgrade(&mut self) {
+        self.assert_owner();
+
+        let code = env::input().expect("No code in the input");
+        assert!(!code.is_empty(), "No code in the input");
+
+        let staged_at = env::block_timestamp();
+        let apply_after = staged_at + upgrade_delay();
+        env::storage_write(STAGED_CODE_KEY, &code);
+        write_u64(STAGED_AT_KEY, staged_at);
+        write_u64(APPLY_AFTER_KEY, apply_after);
+
+        ContractUpgradeStageLog{
+            code_hash: &Base64VecU8(env::sha256(&code)),
+            apply_after: &U64(apply_after),
+        }.emit();
+    }
+
+    //get the staged upgrade
+    pub fn staged_upgrade(&self) -> Option<JsonStagedUpgrade> {
+        let code = env::storage_read(STAGED_CODE_KEY)?;
+        Some(JsonStagedUpgrade {
+            code_hash: Base64VecU8(env::sha256(&code)),
+            staged_at: read_u64(STAGED_AT_KEY).unwrap().into(),
+            apply_after: read_u64(APPLY_AFTER_KEY).unwrap().into(),
+        })
+    }
+
+    //drop the staged upgrade
+    pub fn cancel_upgrade(&mut self) {
+        self.assert_owner();
+
+        let code = env::storage_read(STAGED_CODE_KEY).expect("No staged upgrade");
+        env::storage_remove(STAGED_CODE_KEY);
+        env::storage_remove(STAGED_AT_KEY);
+        env::storage_remove(APPLY_AFTER_KEY);
+
+        ContractUpgradeCancelLog{
+            code_hash: &Base64VecU8(env::sha256(&code)),
+        }.emit();
+    }
+
+    //deploy the staged code once the upgrade delay it was staged with has passed
+    pub fn apply_upgrade(&mut self) -> Promise {
+        self.assert_owner();
+
+        let code = env::storage_read(STAGED_CODE_KEY).expect("No staged upgrade");
+        let apply_after = read_u64(APPLY_AFTER_KEY).unwrap();
+        assert!(env::block_timestamp() >= apply_after, "Upgrade can't be applied before {}", apply_after);
+
+        env::storage_remove(STAGED_CODE_KEY);
+        env::storage_remove(STAGED_AT_KEY);
+        env::storage_remove(APPLY_AFTER_KEY);
+
+        deploy_and_migrate(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use near_sdk::test_utils::{accounts, VMContextBuilder};
+    use near_sdk::testing_env;
+
+    //call the contract as its owner at the block time with the input
+    fn set_context(block_timestamp: u64, input: Vec<u8>) {
+        let mut context = VMContextBuilder::new()
+            .current_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .block_timestamp(block_timestamp)
+            .build();
+        context.input = input;
+        testing_env!(context);
+    }
+
+    fn setup(delay: u64) -> Contract {
+        set_context(0, Vec::new());
+        let mut contract = Contract::new_default_meta(accounts(0));
+        contract.set_upgrade_delay(U64(delay));
+        contract
+    }
+
+    #[test]
+    fn shorter_delay_waits_out_current_delay() {
+        let mut contract = setup(1_000);
+
+        set_context(100, Vec::new());
+        contract.set_upgrade_delay(U64(0));
+        assert_eq!(contract.upgrade_delay(), U64(1_000));
+
+        set_context(1_100, Vec::new());
+        assert_eq!(contract.upgrade_delay(), U64(0));
+    }
+
+    #[test]
+    fn longer_delay_takes_effect_at_once() {
+        let mut contract = setup(1_000);
+
+        contract.set_upgrade_delay(U64(5_000));
+        assert_eq!(contract.upgrade_delay(), U64(5_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "Upgrades are delayed, stage the upgrade first")]
+    fn upgrade_right_after_shortening_delay() {
+        let mut contract = setup(1_000);
+
+        contract.set_upgrade_delay(U64(0));
+        set_context(0, vec![0, 97, 115, 109]);
+        contract.upgrade();
+    }
+
+    #[test]
+    fn staged_upgrade_keeps_its_delay() {
+        let mut contract = setup(1_000);
+
+        set_context(100, vec![0, 97, 115, 109]);
+        contract.stage_upgrade();
+
+        set_context(200, Vec::new());
+        contract.set_upgrade_delay(U64(5_000));
+        assert_eq!(contract.staged_upgrade().unwrap().apply_after, U64(1_100));
+
+        set_context(1_100, Vec::new());
+        contract.apply_upgrade();
+        assert!(contract.staged_upgrade().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Upgrade can't be applied before 1100")]
+    fn apply_upgrade_before_delay() {
+        let mut contract = setup(1_000);
+
+        set_context(100, vec![0, 97, 115, 109]);
+        contract.stage_upgrade();
+
+        set_context(200, Vec::new());
+        contract.set_upgrade_delay(U64(0));
+
+        set_context(300, Vec::new());
+        contract.apply_upgrade();
+    }
+}
@@ -0,0 +1,592 @@
+//! Off-chain indexer of the tournament contract.
+//!
+//! Rebuilds the tournaments, rosters, teams, allowlists, sponsors, guarantees and payouts into SQLite from the `EVENT_JSON:` logs
+//! the contract emits, so the backend doesn't have to poll `display_tournaments`.
+//! Metadata changes (`tournament_update`) only log the names of the changed fields,
+//! so the indexer records when the tournament was updated, not the new values.
+//! Invite codes are logged as hashes only and aren't indexed.
+
+mod schema;
+
+use std::fmt;
+use std::path::Path;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use tournament_test::event_parser::{parse_event_log, EventParseError, TournamentEvent, TournamentEventData, TournamentTeamData};
+
+/// Logs of a receipt executed by the tournament contract
+#[derive(Deserialize, Debug, Clone)]
+pub struct ReceiptLogs {
+    pub block_height: u64,
+    pub receipt_id: String,
+    pub logs: Vec<String>,
+}
+
+/// A tournament as rebuilt from the events
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedTournament {
+    pub tournament_id: String,
+    //unknown for the tournaments created with the version 1.0.0 events
+    pub owner_id: Option<String>,
+    pub pending_owner_id: Option<String>,
+    pub players_number: u32,
+    pub in_price: u128,
+    pub state: String,
+    pub prize_fond: u128,
+}
+
+/// A prize paid to a winner
+#[derive(Debug, Clone, PartialEq)]
+pub struct Payout {
+    pub place: u32,
+    pub account_id: String,
+    pub amount: u128,
+}
+
+/// A sponsor of the prize pool with the sum of its contributions
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sponsor {
+    pub account_id: String,
+    pub amount: u128,
+}
+
+/// The prize fond guaranteed by the owner of the tournament
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guarantee {
+    pub owner_id: String,
+    pub amount: u128,
+    //the part added to the prize fond at payout, known once the tournament ends
+    pub covered: Option<u128>,
+    //the part returned to the owner, known once the tournament ends
+    pub returned: Option<u128>,
+}
+
+/// A team registered by its captain, the captain holds the seat of the team in the roster
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedTeam {
+    pub captain_id: String,
+    pub name: String,
+    pub members: Vec<String>,
+    pub shares: Vec<u16>,
+}
+
+#[derive(Debug)]
+pub enum IndexerError {
+    Sqlite(rusqlite::Error),
+    Event(EventParseError),
+    /// The event refers to a tournament created before the indexed range of logs
+    UnknownTournament(String),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexerError::Sqlite(error) => write!(f, "sqlite error: {}", error),
+            IndexerError::Event(error) => write!(f, "{}", error),
+            IndexerError::UnknownTournament(tournament_id) => write!(f, "unknown tournament: {}", tournament_id),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<rusqlite::Error> for IndexerError {
+    fn from(error: rusqlite::Error) -> Self {
+        IndexerError::Sqlite(error)
+    }
+}
+
+impl From<EventParseError> for IndexerError {
+    fn from(error: EventParseError) -> Self {
+        IndexerError::Event(error)
+    }
+}
+
+pub struct Indexer {
+    conn: Connection,
+}
+
+impl Indexer {
+    /// Opens (or creates) the database at the given path
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Opens a database kept in memory, used for the tests
+    pub fn open_in_memory() -> Result<Self, IndexerError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, IndexerError> {
+        conn.execute_batch(schema::SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// The underlying connection for the custom queries
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Applies the tournament events of the receipt and returns how many were applied.
+    /// The logs of other standards are skipped and a receipt is applied only once.
+    pub fn process_receipt(&mut self, receipt: &ReceiptLogs) -> Result<usize, IndexerError> {
+        let tx = self.conn.transaction()?;
+
+        let processed = tx.query_row(
+            "SELECT 1 FROM processed_receipts WHERE receipt_id = ?1",
+            params![receipt.receipt_id],
+            |_| Ok(()),
+        ).optional()?.is_some();
+        if processed {
+            return Ok(0);
+        }
+
+        let mut applied = 0;
+        for log in &receipt.logs {
+            let event = match parse_event_log(log) {
+                Ok(event) => event,
+                //plain logs and the events of other contracts
+                Err(EventParseError::NotAnEvent) | Err(EventParseError::UnknownStandard(_)) => continue,
+                Err(error) => return Err(error.into()),
+            };
+            apply_event(&tx, receipt.block_height as i64, &event)?;
+            applied += 1;
+        }
+
+        tx.execute(
+            "INSERT INTO processed_receipts (receipt_id, block_height) VALUES (?1, ?2)",
+            params![receipt.receipt_id, receipt.block_height as i64],
+        )?;
+        tx.commit()?;
+
+        Ok(applied)
+    }
+
+    /// Gets the indexed tournament
+    pub fn tournament(&self, tournament_id: &str) -> Result<Option<IndexedTournament>, IndexerError> {
+        Ok(self.conn.query_row(
+            "SELECT tournament_id, owner_id, pending_owner_id, players_number, in_price, state, prize_fond
+             FROM tournaments WHERE tournament_id = ?1",
+            params![tournament_id],
+            |row| Ok(IndexedTournament {
+                tournament_id: row.get(0)?,
+                owner_id: row.get(1)?,
+                pending_owner_id: row.get(2)?,
+                players_number: row.get::<_, u32>(3)?,
+                in_price: parse_amount(row.get(4)?),
+                state: row.get(5)?,
+                prize_fond: parse_amount(row.get(6)?),
+            }),
+        ).optional()?)
+    }
+
+    /// Gets the players of the tournament in the order they joined
+    pub fn roster(&self, tournament_id: &str) -> Result<Vec<String>, IndexerError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT account_id FROM players WHERE tournament_id = ?1 ORDER BY joined_block, rowid"
+        )?;
+        let roster = stmt.query_map(params![tournament_id], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(roster)
+    }
+
+    /// Gets the account that paid for the player, the player itself if nobody else did
+    pub fn payer(&self, tournament_id: &str, account_id: &str) -> Result<Option<String>, IndexerError> {
+        Ok(self.conn.query_row(
+            "SELECT COALESCE(payer_id, account_id) FROM players WHERE tournament_id = ?1 AND account_id = ?2",
+            params![tournament_id, account_id],
+            |row| row.get(0),
+        ).optional()?)
+    }
+
+    /// Gets the teams of the tournament in the order they registered
+    pub fn teams(&self, tournament_id: &str) -> Result<Vec<IndexedTeam>, IndexerError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT teams.captain_id, teams.name, teams.members, teams.shares FROM teams
+             JOIN players ON players.tournament_id = teams.tournament_id AND players.account_id = teams.captain_id
+             WHERE teams.tournament_id = ?1 ORDER BY players.joined_block, players.rowid"
+        )?;
+        let teams = stmt.query_map(params![tournament_id], |row| Ok(IndexedTeam {
+            captain_id: row.get(0)?,
+            name: row.get(1)?,
+            members: parse_json(row.get(2)?),
+            shares: parse_json(row.get(3)?),
+        }))?.collect::<Result<Vec<IndexedTeam>, _>>()?;
+        Ok(teams)
+    }
+
+    /// Gets the allowlist of the tournament in the order the accounts were added
+    pub fn allowlist(&self, tournament_id: &str) -> Result<Vec<String>, IndexerError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT account_id FROM allowlist WHERE tournament_id = ?1 ORDER BY added_block, rowid"
+        )?;
+        let allowlist = stmt.query_map(params![tournament_id], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(allowlist)
+    }
+
+    /// Gets the sponsors of the tournament in the order of their first contributions
+    pub fn sponsors(&self, tournament_id: &str) -> Result<Vec<Sponsor>, IndexerError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT sponsor_id, amount FROM sponsorships WHERE tournament_id = ?1 ORDER BY block_height, rowid"
+        )?;
+        let contributions = stmt.query_map(params![tournament_id], |row| Ok(Sponsor {
+            account_id: row.get(0)?,
+            amount: parse_amount(row.get(1)?),
+        }))?.collect::<Result<Vec<Sponsor>, _>>()?;
+
+        //the amounts are TEXT, so they are summed up here
+        let mut sponsors: Vec<Sponsor> = Vec::new();
+        for contribution in contributions {
+            match sponsors.iter_mut().find(|sponsor| sponsor.account_id == contribution.account_id) {
+                Some(sponsor) => sponsor.amount += contribution.amount,
+                None => sponsors.push(contribution),
+            }
+        }
+        Ok(sponsors)
+    }
+
+    /// Gets the prize fond guaranteed by the owner of the tournament, if any
+    pub fn guarantee(&self, tournament_id: &str) -> Result<Option<Guarantee>, IndexerError> {
+        Ok(self.conn.query_row(
+            "SELECT owner_id, amount, covered, returned FROM guarantees WHERE tournament_id = ?1",
+            params![tournament_id],
+            |row| Ok(Guarantee {
+                owner_id: row.get(0)?,
+                amount: parse_amount(row.get(1)?),
+                covered: row.get::<_, Option<String>>(2)?.map(parse_amount),
+                returned: row.get::<_, Option<String>>(3)?.map(parse_amount),
+            }),
+        ).optional()?)
+    }
+
+    /// Gets the prizes paid in the tournament ordered by place,
+    /// the members of a team share the place of the team
+    pub fn payouts(&self, tournament_id: &str) -> Result<Vec<Payout>, IndexerError> {
+        let mut stmt = self.conn.prepare(
+            "SELECT place, account_id, amount FROM payouts WHERE tournament_id = ?1 ORDER BY place, rowid"
+        )?;
+        let payouts = stmt.query_map(params![tournament_id], |row| Ok(Payout {
+            place: row.get::<_, u32>(0)?,
+            account_id: row.get(1)?,
+            amount: parse_amount(row.get(2)?),
+        }))?.collect::<Result<Vec<Payout>, _>>()?;
+        Ok(payouts)
+    }
+
+    /// Gets the contract owner, if an ownership change was indexed
+    pub fn contract_owner(&self) -> Result<Option<String>, IndexerError> {
+        Ok(self.conn.query_row(
+            "SELECT value FROM contract WHERE key = 'owner_id'",
+            [],
+            |row| row.get(0),
+        ).optional()?)
+    }
+}
+
+//the amounts are written by the indexer itself, so they are always valid
+fn parse_amount(amount: String) -> u128 {
+    amount.parse().expect("Invalid amount in the database")
+}
+
+//the lists are written by the indexer itself, so they are always valid
+fn parse_json<T: serde::de::DeserializeOwned>(json: String) -> T {
+    serde_json::from_str(&json).expect("Invalid JSON in the database")
+}
+
+//save the team as it is after the register or update event
+fn set_team(conn: &Connection, block_height: i64, team: &TournamentTeamData) -> Result<(), IndexerError> {
+    let members: Vec<String> = team.members.iter().map(|member| member.to_string()).collect();
+    conn.execute(
+        "INSERT INTO teams (tournament_id, captain_id, name, members, shares, updated_block)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(tournament_id, captain_id) DO UPDATE SET
+         name = excluded.name, members = excluded.members, shares = excluded.shares, updated_block = excluded.updated_block",
+        params![
+            team.tournament_id,
+            team.captain_id.to_string(),
+            team.name,
+            serde_json::to_string(&members).unwrap(),
+            serde_json::to_string(&team.shares).unwrap(),
+            block_height,
+        ],
+    )?;
+    Ok(())
+}
+
+//get the entrance price and the prize fond of the tournament
+fn tournament_amounts(conn: &Connection, tournament_id: &str) -> Result<(u128, u128), IndexerError> {
+    conn.query_row(
+        "SELECT in_price, prize_fond FROM tournaments WHERE tournament_id = ?1",
+        params![tournament_id],
+        |row| Ok((parse_amount(row.get(0)?), parse_amount(row.get(1)?))),
+    ).optional()?.ok_or_else(|| IndexerError::UnknownTournament(tournament_id.to_string()))
+}
+
+//change the prize fond of the tournament
+fn update_prize_fond(
+    conn: &Connection,
+    block_height: i64,
+    tournament_id: &str,
+    update: impl FnOnce(u128, u128) -> u128,
+) -> Result<(), IndexerError> {
+    let (in_price, prize_fond) = tournament_amounts(conn, tournament_id)?;
+    conn.execute(
+        "UPDATE tournaments SET prize_fond = ?2, updated_block = ?3 WHERE tournament_id = ?1",
+        params![tournament_id, update(in_price, prize_fond).to_string(), block_height],
+    )?;
+    Ok(())
+}
+
+//change the state of the tournament
+fn set_state(conn: &Connection, block_height: i64, tournament_id: &str, state: &str) -> Result<(), IndexerError> {
+    let updated = conn.execute(
+        "UPDATE tournaments SET state = ?2, updated_block = ?3 WHERE tournament_id = ?1",
+        params![tournament_id, state, block_height],
+    )?;
+    ensure_updated(updated, tournament_id)
+}
+
+//fail when the update of the tournament row didn't find the tournament
+fn ensure_updated(updated: usize, tournament_id: &str) -> Result<(), IndexerError> {
+    if updated == 0 {
+        return Err(IndexerError::UnknownTournament(tournament_id.to_string()));
+    }
+    Ok(())
+}
+
+fn set_contract_value(conn: &Connection, key: &str, value: Option<String>) -> Result<(), IndexerError> {
+    conn.execute(
+        "INSERT INTO contract (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
+//pause or unpause the contract (no tournament) or the tournament
+fn set_paused(conn: &Connection, tournament_id: Option<&str>, paused: bool) -> Result<(), IndexerError> {
+    if let Some(tournament_id) = tournament_id {
+        conn.execute(
+            "UPDATE tournaments SET paused = ?2 WHERE tournament_id = ?1",
+            params![tournament_id, paused],
+        )?;
+        Ok(())
+    } else {
+        set_contract_value(conn, "paused", Some(paused.to_string()))
+    }
+}
+
+//apply a single event to the database
+fn apply_event(conn: &Connection, block_height: i64, event: &TournamentEvent) -> Result<(), IndexerError> {
+    match &event.event {
+        TournamentEventData::TournamentCreate(data) => {
+            for create in data {
+                conn.execute(
+                    "INSERT INTO tournaments
+                     (tournament_id, owner_id, players_number, in_price, state, prize_fond, created_block, updated_block)
+                     VALUES (?1, ?2, ?3, ?4, 'open', '0', ?5, ?5)",
+                    params![
+                        create.tournament_id,
+                        create.owner_id.as_ref().map(|owner_id| owner_id.to_string()),
+                        i64::from(create.players_number),
+                        create.in_price.0.to_string(),
+                        block_height,
+                    ],
+                )?;
+            }
+        }
+        TournamentEventData::TournamentEntrance(data) => {
+            for entrance in data {
+                conn.execute(
+                    "INSERT INTO players (tournament_id, account_id, payer_id, joined_block) VALUES (?1, ?2, ?3, ?4)",
+                    params![
+                        entrance.tournament_id,
+                        entrance.partisipator_id.to_string(),
+                        entrance.payer_id.as_ref().map(|payer_id| payer_id.to_string()),
+                        block_height,
+                    ],
+                )?;
+                update_prize_fond(conn, block_height, &entrance.tournament_id, |in_price, prize_fond| prize_fond + in_price)?;
+            }
+        }
+        TournamentEventData::TournamentPrizesReward(data) => {
+            for reward in data {
+                for winner in &reward.winners {
+                    conn.execute(
+                        "INSERT INTO payouts (tournament_id, place, account_id, amount, block_height) VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![
+                            reward.tournament_id,
+                            i64::from(winner.place),
+                            winner.account_id.to_string(),
+                            winner.amount.0.to_string(),
+                            block_height,
+                        ],
+                    )?;
+                }
+                let rewarded_amount = reward.rewarded_amount.0;
+                update_prize_fond(conn, block_height, &reward.tournament_id, |_, prize_fond| prize_fond.saturating_sub(rewarded_amount))?;
+                set_state(conn, block_height, &reward.tournament_id, "completed")?;
+            }
+        }
+        TournamentEventData::TournamentUpdate(data) => {
+            for update in data {
+                conn.execute(
+                    "UPDATE tournaments SET updated_block = ?2 WHERE tournament_id = ?1",
+                    params![update.tournament_id, block_height],
+                )?;
+            }
+        }
+        TournamentEventData::TournamentOwnerPropose(data) => {
+            for propose in data {
+                let updated = conn.execute(
+                    "UPDATE tournaments SET pending_owner_id = ?2, updated_block = ?3 WHERE tournament_id = ?1",
+                    params![propose.tournament_id, propose.new_owner_id.to_string(), block_height],
+                )?;
+                ensure_updated(updated, &propose.tournament_id)?;
+            }
+        }
+        TournamentEventData::TournamentOwnerAccept(data) => {
+            for accept in data {
+                let updated = conn.execute(
+                    "UPDATE tournaments SET owner_id = ?2, pending_owner_id = NULL, updated_block = ?3 WHERE tournament_id = ?1",
+                    params![accept.tournament_id, accept.new_owner_id.to_string(), block_height],
+                )?;
+                ensure_updated(updated, &accept.tournament_id)?;
+            }
+        }
+        TournamentEventData::ContractOwnerPropose(data) => {
+            for propose in data {
+                set_contract_value(conn, "owner_id", Some(propose.old_owner_id.to_string()))?;
+                set_contract_value(conn, "pending_owner_id", Some(propose.new_owner_id.to_string()))?;
+            }
+        }
+        TournamentEventData::ContractOwnerAccept(data) => {
+            for accept in data {
+                set_contract_value(conn, "owner_id", Some(accept.new_owner_id.to_string()))?;
+                set_contract_value(conn, "pending_owner_id", None)?;
+            }
+        }
+        TournamentEventData::TournamentOwnerCancel(data) => {
+            for cancel in data {
+                let updated = conn.execute(
+                    "UPDATE tournaments SET pending_owner_id = NULL, updated_block = ?2 WHERE tournament_id = ?1",
+                    params![cancel.tournament_id, block_height],
+                )?;
+                ensure_updated(updated, &cancel.tournament_id)?;
+            }
+        }
+        TournamentEventData::ContractOwnerCancel(_) => {
+            set_contract_value(conn, "pending_owner_id", None)?;
+        }
+        TournamentEventData::TournamentCancel(data) => {
+            for cancel in data {
+                let refunded_amount = cancel.refunded_amount.0;
+                update_prize_fond(conn, block_height, &cancel.tournament_id, |_, prize_fond| prize_fond.saturating_sub(refunded_amount))?;
+                set_state(conn, block_height, &cancel.tournament_id, "cancelled")?;
+            }
+        }
+        TournamentEventData::TournamentLeave(data) => {
+            for leave in data {
+                conn.execute(
+                    "DELETE FROM players WHERE tournament_id = ?1 AND account_id = ?2",
+                    params![leave.tournament_id, leave.player_id.to_string()],
+                )?;
+                //the team leaves with its captain
+                conn.execute(
+                    "DELETE FROM teams WHERE tournament_id = ?1 AND captain_id = ?2",
+                    params![leave.tournament_id, leave.player_id.to_string()],
+                )?;
+                update_prize_fond(conn, block_height, &leave.tournament_id, |in_price, prize_fond| prize_fond.saturating_sub(in_price))?;
+            }
+        }
+        TournamentEventData::TournamentRefund(data) => {
+            //the prize fond is decreased by the cancel and leave events themselves
+            for refund in data {
+                conn.execute(
+                    "INSERT INTO refunds (tournament_id, account_id, amount, block_height) VALUES (?1, ?2, ?3, ?4)",
+                    params![refund.tournament_id, refund.account_id.to_string(), refund.amount.0.to_string(), block_height],
+                )?;
+            }
+        }
+        TournamentEventData::TournamentFeesWithdraw(data) => {
+            for withdraw in data {
+                conn.execute(
+                    "INSERT INTO fee_withdrawals (tournament_id, owner_id, amount, block_height) VALUES (?1, ?2, ?3, ?4)",
+                    params![withdraw.tournament_id, withdraw.owner_id.to_string(), withdraw.amount.0.to_string(), block_height],
+                )?;
+                let amount = withdraw.amount.0;
+                update_prize_fond(conn, block_height, &withdraw.tournament_id, |_, prize_fond| prize_fond.saturating_sub(amount))?;
+            }
+        }
+        TournamentEventData::ContractPause(data) => {
+            for pause in data {
+                set_paused(conn, pause.tournament_id.as_deref(), true)?;
+            }
+        }
+        TournamentEventData::ContractUnpause(data) => {
+            for unpause in data {
+                set_paused(conn, unpause.tournament_id.as_deref(), false)?;
+            }
+        }
+        TournamentEventData::TournamentTeamRegister(data) | TournamentEventData::TournamentTeamUpdate(data) => {
+            for team in data {
+                set_team(conn, block_height, team)?;
+            }
+        }
+        TournamentEventData::TournamentAllowlistUpdate(data) => {
+            for update in data {
+                for account_id in update.added.iter() {
+                    conn.execute(
+                        "INSERT OR IGNORE INTO allowlist (tournament_id, account_id, added_block) VALUES (?1, ?2, ?3)",
+                        params![update.tournament_id, account_id.to_string(), block_height],
+                    )?;
+                }
+                for account_id in update.removed.iter() {
+                    conn.execute(
+                        "DELETE FROM allowlist WHERE tournament_id = ?1 AND account_id = ?2",
+                        params![update.tournament_id, account_id.to_string()],
+                    )?;
+                }
+            }
+        }
+        TournamentEventData::TournamentSponsor(data) => {
+            for sponsorship in data {
+                conn.execute(
+                    "INSERT INTO sponsorships (tournament_id, sponsor_id, amount, block_height) VALUES (?1, ?2, ?3, ?4)",
+                    params![sponsorship.tournament_id, sponsorship.sponsor_id.to_string(), sponsorship.amount.0.to_string(), block_height],
+                )?;
+                let amount = sponsorship.amount.0;
+                update_prize_fond(conn, block_height, &sponsorship.tournament_id, |_, prize_fond| prize_fond + amount)?;
+            }
+        }
+        TournamentEventData::TournamentGuarantee(data) => {
+            for guarantee in data {
+                conn.execute(
+                    "INSERT INTO guarantees (tournament_id, owner_id, amount, block_height) VALUES (?1, ?2, ?3, ?4)",
+                    params![guarantee.tournament_id, guarantee.owner_id.to_string(), guarantee.amount.0.to_string(), block_height],
+                )?;
+            }
+        }
+        TournamentEventData::TournamentGuaranteeSettle(data) => {
+            for settle in data {
+                conn.execute(
+                    "UPDATE guarantees SET covered = ?2, returned = ?3 WHERE tournament_id = ?1",
+                    params![settle.tournament_id, settle.covered.0.to_string(), settle.returned.0.to_string()],
+                )?;
+                //the covered part is paid out with the prizes logged next
+                let covered = settle.covered.0;
+                update_prize_fond(conn, block_height, &settle.tournament_id, |_, prize_fond| prize_fond + covered)?;
+            }
+        }
+        //code upgrades, invite codes, rejected entrances (refunded with their own event) and
+        //locked payout tiers (the prizes are logged by the payout) don't change the indexed state
+        TournamentEventData::ContractUpgradeStage(_)
+        | TournamentEventData::ContractUpgradeCancel(_)
+        | TournamentEventData::ContractUpgrade(_)
+        | TournamentEventData::TournamentInviteCodesUpdate(_)
+        | TournamentEventData::TournamentEntryReject(_)
+        | TournamentEventData::TournamentPayoutTierLock(_) => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+//! Reads the receipt logs of the tournament contract as JSON lines from stdin,
+//! e.g. `{"block_height": 1, "receipt_id": "...", "logs": ["EVENT_JSON:..."]}`,
+//! and indexes them into the SQLite database given as the argument.
+
+use std::io::{self, BufRead};
+use std::process;
+use tournament_indexer::{Indexer, ReceiptLogs};
+
+fn main() {
+    let path = match std::env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: tournament-indexer <database.sqlite> < receipts.jsonl");
+            process::exit(2);
+        }
+    };
+
+    let mut indexer = Indexer::open(&path).unwrap_or_else(|error| {
+        eprintln!("Can't open {}: {}", path, error);
+        process::exit(1);
+    });
+
+    let stdin = io::stdin();
+    for (number, line) in stdin.lock().lines().enumerate() {
+        let line = line.unwrap_or_else(|error| {
+            eprintln!("Can't read stdin: {}", error);
+            process::exit(1);
+        });
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let receipt: ReceiptLogs = serde_json::from_str(&line).unwrap_or_else(|error| {
+            eprintln!("Line {}: invalid receipt: {}", number + 1, error);
+            process::exit(1);
+        });
+
+        if let Err(error) = indexer.process_receipt(&receipt) {
+            eprintln!("Receipt {}: {}", receipt.receipt_id, error);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! SQLite schema of the indexed state.
+//!
+//! NEAR amounts are stored as decimal TEXT since they don't fit into the SQLite INTEGER.
+
+pub(crate) const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS processed_receipts (
+    receipt_id      TEXT PRIMARY KEY,
+    block_height    INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS contract (
+    key             TEXT PRIMARY KEY,
+    value           TEXT
+);
+
+CREATE TABLE IF NOT EXISTS tournaments (
+    tournament_id       TEXT PRIMARY KEY,
+    owner_id            TEXT,
+    pending_owner_id    TEXT,
+    players_number      INTEGER NOT NULL,
+    in_price            TEXT NOT NULL,
+    state               TEXT NOT NULL,
+    prize_fond          TEXT NOT NULL,
+    paused              INTEGER NOT NULL DEFAULT 0,
+    created_block       INTEGER NOT NULL,
+    updated_block       INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS players (
+    tournament_id   TEXT NOT NULL,
+    account_id      TEXT NOT NULL,
+    -- the account that paid for the player, NULL when the player paid
+    payer_id        TEXT,
+    joined_block    INTEGER NOT NULL,
+    PRIMARY KEY (tournament_id, account_id)
+);
+
+CREATE TABLE IF NOT EXISTS payouts (
+    tournament_id   TEXT NOT NULL,
+    place           INTEGER NOT NULL,
+    account_id      TEXT NOT NULL,
+    amount          TEXT NOT NULL,
+    block_height    INTEGER NOT NULL,
+    PRIMARY KEY (tournament_id, place, account_id)
+);
+
+CREATE TABLE IF NOT EXISTS teams (
+    tournament_id   TEXT NOT NULL,
+    captain_id      TEXT NOT NULL,
+    name            TEXT NOT NULL,
+    -- JSON arrays of the members and their prize shares
+    members         TEXT NOT NULL,
+    shares          TEXT NOT NULL,
+    updated_block   INTEGER NOT NULL,
+    PRIMARY KEY (tournament_id, captain_id)
+);
+
+CREATE TABLE IF NOT EXISTS allowlist (
+    tournament_id   TEXT NOT NULL,
+    account_id      TEXT NOT NULL,
+    added_block     INTEGER NOT NULL,
+    PRIMARY KEY (tournament_id, account_id)
+);
+
+-- one row per contribution, the sponsors are refunded with the cancellation
+CREATE TABLE IF NOT EXISTS sponsorships (
+    tournament_id   TEXT NOT NULL,
+    sponsor_id      TEXT NOT NULL,
+    amount          TEXT NOT NULL,
+    block_height    INTEGER NOT NULL
+);
+
+-- the prize fond guaranteed by the owner, covered and returned are NULL until the tournament ends
+CREATE TABLE IF NOT EXISTS guarantees (
+    tournament_id   TEXT PRIMARY KEY,
+    owner_id        TEXT NOT NULL,
+    amount          TEXT NOT NULL,
+    covered         TEXT,
+    returned        TEXT,
+    block_height    INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS refunds (
+    tournament_id   TEXT NOT NULL,
+    account_id      TEXT NOT NULL,
+    amount          TEXT NOT NULL,
+    block_height    INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS fee_withdrawals (
+    tournament_id   TEXT NOT NULL,
+    owner_id        TEXT NOT NULL,
+    amount          TEXT NOT NULL,
+    block_height    INTEGER NOT NULL
+);
+";
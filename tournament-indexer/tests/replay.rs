@@ -0,0 +1,224 @@
+use tournament_indexer::{Guarantee, IndexedTeam, Indexer, Payout, ReceiptLogs, Sponsor};
+
+const NEAR_0_1: u128 = 100_000_000_000_000_000_000_000;
+
+fn receipts(fixture: &str) -> Vec<ReceiptLogs> {
+    fixture.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn replay(fixture: &str) -> Indexer {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    for receipt in receipts(fixture) {
+        indexer.process_receipt(&receipt).unwrap();
+    }
+    indexer
+}
+
+#[test]
+fn rebuilds_completed_tournament() {
+    let indexer = replay(include_str!("fixtures/flow.jsonl"));
+
+    let tournament = indexer.tournament("tournament-1").unwrap().unwrap();
+    assert_eq!(tournament.owner_id.as_deref(), Some("tournament.testnet"));
+    assert_eq!(tournament.players_number, 8);
+    assert_eq!(tournament.in_price, NEAR_0_1);
+    assert_eq!(tournament.state, "completed");
+    //the rest of the prize fond is withdrawn by the owner
+    assert_eq!(tournament.prize_fond, 0);
+
+    assert_eq!(
+        indexer.roster("tournament-1").unwrap(),
+        vec!["alice.testnet", "bob.testnet", "carol.testnet"]
+    );
+    assert_eq!(
+        indexer.payouts("tournament-1").unwrap(),
+        vec![
+            Payout { place: 1, account_id: "bob.testnet".to_string(), amount: 150_000_000_000_000_000_000_000 },
+            Payout { place: 2, account_id: "alice.testnet".to_string(), amount: 90_000_000_000_000_000_000_000 },
+        ]
+    );
+}
+
+#[test]
+fn rebuilds_cancelled_tournament() {
+    let indexer = replay(include_str!("fixtures/flow.jsonl"));
+
+    let tournament = indexer.tournament("tournament-2").unwrap().unwrap();
+    assert_eq!(tournament.owner_id.as_deref(), Some("dave.testnet"));
+    assert_eq!(tournament.pending_owner_id, None);
+    assert_eq!(tournament.state, "cancelled");
+    assert_eq!(tournament.prize_fond, 0);
+
+    //alice left before the cancellation
+    assert_eq!(indexer.roster("tournament-2").unwrap(), vec!["dave.testnet"]);
+    assert!(indexer.payouts("tournament-2").unwrap().is_empty());
+
+    let refunds: i64 = indexer.connection()
+        .query_row("SELECT COUNT(*) FROM refunds WHERE tournament_id = 'tournament-2'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(refunds, 2);
+
+    assert_eq!(indexer.contract_owner().unwrap().as_deref(), Some("admin.testnet"));
+}
+
+#[test]
+fn skips_foreign_logs_and_replayed_receipts() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let receipts = receipts(include_str!("fixtures/flow.jsonl"));
+
+    //the receipt creating tournament-2 has a plain log and an NFT event as well
+    assert_eq!(indexer.process_receipt(&receipts[0]).unwrap(), 1);
+    assert_eq!(indexer.process_receipt(&receipts[6]).unwrap(), 1);
+
+    //receipts can be delivered twice by the stream, they are applied once
+    assert_eq!(indexer.process_receipt(&receipts[0]).unwrap(), 0);
+    assert!(indexer.tournament("tournament-1").unwrap().is_some());
+}
+
+#[test]
+fn reads_legacy_v1_events() {
+    let indexer = replay(include_str!("fixtures/legacy_v1.jsonl"));
+
+    let tournament = indexer.tournament("tournament-0").unwrap().unwrap();
+    assert_eq!(tournament.owner_id, None);
+    assert_eq!(tournament.state, "completed");
+    assert_eq!(tournament.prize_fond, 200);
+    //the version 1.0.0 reward events don't say who won
+    assert!(indexer.payouts("tournament-0").unwrap().is_empty());
+}
+
+#[test]
+fn fails_on_unknown_tournament() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    let receipts = receipts(include_str!("fixtures/flow.jsonl"));
+
+    //an entrance without the creation of the tournament
+    assert!(indexer.process_receipt(&receipts[1]).is_err());
+    //the failed receipt isn't marked as processed
+    indexer.process_receipt(&receipts[0]).unwrap();
+    assert_eq!(indexer.process_receipt(&receipts[1]).unwrap(), 1);
+}
+
+#[test]
+fn fails_on_owner_change_of_unknown_tournament() {
+    let mut indexer = Indexer::open_in_memory().unwrap();
+    //the owner change of the tournament which creation wasn't indexed
+    let receipts = receipts(include_str!("fixtures/owner_change.jsonl"));
+
+    assert!(indexer.process_receipt(&receipts[0]).is_err());
+    assert!(indexer.process_receipt(&receipts[1]).is_err());
+}
+
+#[test]
+fn clears_cancelled_owner_proposal() {
+    let indexer = replay(include_str!("fixtures/owner_cancel.jsonl"));
+
+    let tournament = indexer.tournament("tournament-1").unwrap().unwrap();
+    assert_eq!(tournament.owner_id.as_deref(), Some("tournament.testnet"));
+    assert_eq!(tournament.pending_owner_id, None);
+}
+
+#[test]
+fn rebuilds_team_tournament() {
+    let indexer = replay(include_str!("fixtures/teams.jsonl"));
+
+    //the captains hold the seats of the teams
+    assert_eq!(indexer.roster("tournament-3").unwrap(), vec!["alice.testnet", "carol.testnet"]);
+    assert_eq!(
+        indexer.teams("tournament-3").unwrap(),
+        vec![
+            IndexedTeam {
+                captain_id: "alice.testnet".to_string(),
+                name: "Blue".to_string(),
+                members: vec!["alice.testnet".to_string(), "erin.testnet".to_string()],
+                shares: vec![3, 1],
+            },
+            IndexedTeam {
+                captain_id: "carol.testnet".to_string(),
+                name: "Red".to_string(),
+                members: vec!["carol.testnet".to_string(), "dave.testnet".to_string()],
+                shares: vec![1, 1],
+            },
+        ]
+    );
+
+    //the prize of a team is split between its members at the same place
+    let payouts = indexer.payouts("tournament-3").unwrap();
+    assert_eq!(payouts.len(), 4);
+    assert_eq!(payouts[1], Payout { place: 1, account_id: "erin.testnet".to_string(), amount: 25_000_000_000_000_000_000_000 });
+    assert_eq!(indexer.tournament("tournament-3").unwrap().unwrap().prize_fond, 2 * NEAR_0_1 - 120_000_000_000_000_000_000_000);
+}
+
+#[test]
+fn records_payers_of_sponsored_players() {
+    let indexer = replay(include_str!("fixtures/sponsored.jsonl"));
+
+    assert_eq!(indexer.roster("tournament-4").unwrap(), vec!["alice.testnet", "bob.testnet"]);
+    assert_eq!(indexer.payer("tournament-4", "alice.testnet").unwrap().as_deref(), Some("guild.testnet"));
+    assert_eq!(indexer.payer("tournament-4", "bob.testnet").unwrap().as_deref(), Some("bob.testnet"));
+    assert_eq!(indexer.payer("tournament-4", "carol.testnet").unwrap(), None);
+
+    //carol left the seat paid by the guild, the guild got the refund
+    let refunded: String = indexer.connection()
+        .query_row("SELECT account_id FROM refunds WHERE tournament_id = 'tournament-4'", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(refunded, "guild.testnet");
+}
+
+#[test]
+fn tracks_allowlist() {
+    let indexer = replay(include_str!("fixtures/allowlist.jsonl"));
+
+    assert_eq!(indexer.allowlist("tournament-5").unwrap(), vec!["alice.testnet", "carol.testnet"]);
+    assert_eq!(indexer.roster("tournament-5").unwrap(), vec!["alice.testnet"]);
+}
+
+#[test]
+fn tracks_sponsors_of_free_tournament() {
+    let indexer = replay(include_str!("fixtures/free.jsonl"));
+
+    assert_eq!(indexer.sponsors("tournament-6").unwrap(), vec![
+        Sponsor { account_id: "guild.testnet".to_string(), amount: 8 * NEAR_0_1 },
+        Sponsor { account_id: "shop.testnet".to_string(), amount: 2 * NEAR_0_1 },
+    ]);
+    assert_eq!(indexer.roster("tournament-6").unwrap(), vec!["alice.testnet"]);
+
+    //the sponsors got their contributions back
+    let tournament = indexer.tournament("tournament-6").unwrap().unwrap();
+    assert_eq!(tournament.in_price, 0);
+    assert_eq!(tournament.state, "cancelled");
+    assert_eq!(tournament.prize_fond, 0);
+}
+
+#[test]
+fn guarantee_tops_up_prize_fond() {
+    let indexer = replay(include_str!("fixtures/guaranteed.jsonl"));
+
+    assert_eq!(indexer.guarantee("tournament-7").unwrap(), Some(Guarantee {
+        owner_id: "tournament.testnet".to_string(),
+        amount: 10 * NEAR_0_1,
+        covered: Some(8 * NEAR_0_1),
+        returned: Some(2 * NEAR_0_1),
+    }));
+    assert_eq!(indexer.guarantee("tournament-6").unwrap(), None);
+
+    //the fee of the guaranteed prize fond is left for the owner
+    let tournament = indexer.tournament("tournament-7").unwrap().unwrap();
+    assert_eq!(tournament.state, "completed");
+    assert_eq!(tournament.prize_fond, NEAR_0_1);
+}
+
+#[test]
+fn rebuilds_qualifier_above_255_players() {
+    let indexer = replay(include_str!("fixtures/qualifier.jsonl"));
+
+    let tournament = indexer.tournament("tournament-8").unwrap().unwrap();
+    assert_eq!(tournament.players_number, 4_096);
+    assert_eq!(tournament.prize_fond, NEAR_0_1);
+
+    let payouts = indexer.payouts("tournament-8").unwrap();
+    assert_eq!(payouts[1], Payout { place: 300, account_id: "alice.testnet".to_string(), amount: NEAR_0_1 });
+}
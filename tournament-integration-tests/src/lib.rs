@@ -0,0 +1,9 @@
+//! Integration tests of the tournament contract against a local sandbox node.
+//!
+//! The tests live in `tests/` and deploy `out/main.wasm`, so build the contract first.
+//! The crate is a workspace of its own, `near-workspaces` needs a newer toolchain than the contract:
+//!
+//! ```text
+//! cd tournament-contract && ./build.sh
+//! cd ../tournament-integration-tests && cargo test -- --ignored
+//! ```
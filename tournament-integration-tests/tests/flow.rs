@@ -0,0 +1,152 @@
+//! The create - participate - reward flow of `Calls.txt` on a sandbox node,
+//! checking the NEAR moved between the accounts.
+
+use near_workspaces::types::NearToken;
+use near_workspaces::{Account, Contract};
+use serde_json::{json, Value};
+
+/// The contract built by `tournament-contract/build.sh`
+const WASM_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../out/main.wasm");
+
+/// Upper bound of the gas paid by an account for a single call
+const GAS_TOLERANCE: NearToken = NearToken::from_millinear(10);
+
+const TOURNAMENT_ID: &str = "tournament-1";
+
+async fn balance(account: &Account) -> anyhow::Result<u128> {
+    Ok(account.view_account().await?.balance.as_yoctonear())
+}
+
+async fn display_tournament(contract: &Contract) -> anyhow::Result<Value> {
+    Ok(contract
+        .view("display_tournament")
+        .args_json(json!({ "tournament_id": TOURNAMENT_ID }))
+        .await?
+        .json()?)
+}
+
+//check the balance has changed by `expected` minus the gas paid by the account
+fn assert_paid(before: u128, after: u128, expected: i128, account: &str) {
+    let change = after as i128 - before as i128;
+    let gas = expected - change;
+    assert!(
+        gas >= 0 && gas <= GAS_TOLERANCE.as_yoctonear() as i128,
+        "{} balance changed by {} instead of {}", account, change, expected
+    );
+}
+
+#[tokio::test]
+#[ignore = "needs out/main.wasm and the sandbox binary"]
+async fn create_participate_reward() -> anyhow::Result<()> {
+    let worker = near_workspaces::sandbox().await?;
+    let wasm = std::fs::read(WASM_PATH)
+        .unwrap_or_else(|_| panic!("No {}, run tournament-contract/build.sh first", WASM_PATH));
+    let contract = worker.dev_deploy(&wasm).await?;
+
+    let root = worker.root_account()?;
+    let mut accounts = Vec::new();
+    for name in ["owner", "alice", "bob"] {
+        accounts.push(
+            root.create_subaccount(name)
+                .initial_balance(NearToken::from_near(50))
+                .transact()
+                .await?
+                .into_result()?,
+        );
+    }
+    let (owner, alice, bob) = (&accounts[0], &accounts[1], &accounts[2]);
+
+    contract
+        .call("new_default_meta")
+        .args_json(json!({ "owner_id": owner.id() }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let in_price = NearToken::from_near(10).as_yoctonear();
+
+    //create
+    owner
+        .call(contract.id(), "tournament_create")
+        .args_json(json!({
+            "args": {
+                "tournament_id": TOURNAMENT_ID,
+                "name": "Tournament-1",
+                "players_number": 8,
+                "in_price": in_price.to_string(),
+                "tournament_owner_id": owner.id(),
+                "basis_points_map": { "1": 5000, "2": 3000, "3": 1000 },
+            },
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    //participate, bob attaches more and gets the rest back
+    let alice_before = balance(alice).await?;
+    alice
+        .call(contract.id(), "participate_tournament")
+        .args_json(json!({ "tournament_id": TOURNAMENT_ID }))
+        .deposit(NearToken::from_yoctonear(in_price))
+        .transact()
+        .await?
+        .into_result()?;
+    assert_paid(alice_before, balance(alice).await?, -(in_price as i128), "alice");
+
+    let bob_before = balance(bob).await?;
+    bob
+        .call(contract.id(), "participate_tournament")
+        .args_json(json!({ "tournament_id": TOURNAMENT_ID }))
+        .deposit(NearToken::from_near(15))
+        .transact()
+        .await?
+        .into_result()?;
+    assert_paid(bob_before, balance(bob).await?, -(in_price as i128), "bob");
+
+    let tournament = display_tournament(&contract).await?;
+    assert_eq!(tournament["prize_fond"], json!((2 * in_price).to_string()));
+    assert_eq!(tournament["state"], json!("open"));
+
+    //reward, the winners don't pay for the call so they get the exact prizes
+    let alice_before = balance(alice).await?;
+    let bob_before = balance(bob).await?;
+    let contract_before = balance(contract.as_account()).await?;
+    owner
+        .call(contract.id(), "reward_prizes")
+        .args_json(json!({
+            "tournament_id": TOURNAMENT_ID,
+            "winners_map": { "1": bob.id(), "2": alice.id() },
+        }))
+        .max_gas()
+        .transact()
+        .await?
+        .into_result()?;
+    assert_eq!(balance(bob).await? - bob_before, in_price);
+    assert_eq!(balance(alice).await? - alice_before, in_price * 6 / 10);
+    //the contract also gets a part of the burnt gas as the reward
+    assert_paid(
+        balance(contract.as_account()).await?,
+        contract_before,
+        (in_price * 16 / 10) as i128,
+        "contract",
+    );
+
+    let tournament = display_tournament(&contract).await?;
+    assert_eq!(tournament["state"], json!("completed"));
+    assert_eq!(tournament["prize_fond"], json!((in_price * 4 / 10).to_string()));
+
+    //the owner takes the rest of the prize fond
+    let owner_before = balance(owner).await?;
+    owner
+        .call(contract.id(), "withdraw_fees")
+        .args_json(json!({ "tournament_id": TOURNAMENT_ID }))
+        .transact()
+        .await?
+        .into_result()?;
+    assert_paid(owner_before, balance(owner).await?, (in_price * 4 / 10) as i128, "owner");
+
+    let tournament = display_tournament(&contract).await?;
+    assert_eq!(tournament["prize_fond"], json!("0"));
+
+    Ok(())
+}